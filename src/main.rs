@@ -1,144 +1,359 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, flaky, history, read, robust, scaffold, solve, time, verify,
+};
+use advent_of_code::template::{config, output, Day, Error};
 use args::{parse, AppArguments};
-
-#[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        input::InputSource, output, profile, report::ReportTarget, submissions::SubmitTarget, Day,
+        DaySet,
+    };
+    use std::{env, time::Duration};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            days: Vec<Day>,
         },
         Read {
-            day: Day,
+            days: Vec<Day>,
         },
         Scaffold {
-            day: Day,
+            days: Vec<Day>,
             download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            days: Vec<Day>,
             release: bool,
+            checked: bool,
             dhat: bool,
-            submit: Option<u8>,
+            check: bool,
+            submit: Option<SubmitTarget>,
+            force: bool,
+            input: InputSource,
         },
         All {
+            days: Vec<Day>,
             release: bool,
+            checked: bool,
+            check: bool,
+            timeout: Option<Duration>,
+            reports: Vec<ReportTarget>,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            days: Option<Vec<Day>>,
             store: bool,
+            compare: bool,
+            fail_on_regression: Option<f64>,
+            enforce_budget: bool,
+        },
+        TimingsHistory {
+            days: Vec<Day>,
+        },
+        Verify {
+            days: Vec<Day>,
+            release: bool,
+        },
+        Robust {
+            days: Vec<Day>,
+            release: bool,
+            raw: bool,
+        },
+        Flaky {
+            days: Vec<Day>,
+            runs: usize,
+            perturb: bool,
+            release: bool,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Resolves a parsed set of days like `3`, `1..=5` or `unsolved` to the days it selects.
+    fn resolve_days(set: DaySet) -> Result<Vec<Day>, Box<dyn std::error::Error>> {
+        Ok(set.resolve()?)
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
+        let subcommand = args.subcommand()?;
+
+        // child processes (e.g. solutions submitting answers) inherit the active profile.
+        if let Some(name) = args.opt_value_from_str::<_, String>("--profile")? {
+            profile::validate_name(&name)?;
+            env::set_var(profile::PROFILE_ENV, name);
+        }
+
+        // the profile can also be set in the environment, which is used to build paths of inputs and answers.
+        profile::validate_active()?;
+
+        if let Some(format) = args.opt_value_from_str("--format")? {
+            output::set_format(format);
+        }
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                checked: args.contains("--checked"),
+                check: args.contains("--check"),
+                reports: args.values_from_str("--report")?,
+                timeout: args
+                    .opt_value_from_str::<_, u64>("--timeout")?
+                    .map(Duration::from_secs),
+                days: resolve_days(args.opt_free_from_str()?.unwrap_or(DaySet::All))?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let enforce_budget = args.contains("--enforce-budget");
+                let fail_on_regression: Option<f64> =
+                    args.opt_value_from_str("--fail-on-regression")?;
+
+                if fail_on_regression.is_some_and(|x| x.is_nan() || x < 0.0) {
+                    return Err(
+                        "`--fail-on-regression` has to be a non-negative percentage.".into(),
+                    );
+                }
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    days: args.opt_free_from_str()?.map(resolve_days).transpose()?,
                     store,
+                    compare,
+                    fail_on_regression,
+                    enforce_budget,
                 }
             }
+            Some("timings") => match args.free_from_str::<String>()?.as_str() {
+                "history" => AppArguments::TimingsHistory {
+                    days: resolve_days(args.free_from_str()?)?,
+                },
+                x => return Err(format!("Unknown timings command: {x}").into()),
+            },
+            Some("verify") => AppArguments::Verify {
+                release: args.contains("--release"),
+                days: resolve_days(args.free_from_str()?)?,
+            },
+            Some("robust") => AppArguments::Robust {
+                release: args.contains("--release"),
+                raw: args.contains("--raw"),
+                days: resolve_days(args.free_from_str()?)?,
+            },
+            Some("flaky") => AppArguments::Flaky {
+                release: args.contains("--release"),
+                perturb: args.contains("--perturb"),
+                runs: args.opt_value_from_str("--runs")?.unwrap_or(5),
+                days: resolve_days(args.free_from_str()?)?,
+            },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                days: resolve_days(args.free_from_str()?)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                days: resolve_days(args.free_from_str()?)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                days: resolve_days(args.free_from_str()?)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let release = args.contains("--release");
+                let checked = args.contains("--checked");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let check = args.contains("--check");
+                let force = args.contains("--force");
+                let days = resolve_days(args.free_from_str()?)?;
+                // the example name is optional and remains as a free argument after the day.
+                let input = InputSource::from_args(&mut args)?;
+
+                if dhat && checked {
+                    return Err("`--dhat` and `--checked` are mutually exclusive.".into());
+                }
+
+                if submit.is_some() && input != InputSource::Puzzle {
+                    return Err("`--submit` can only be used with the puzzle input.".into());
+                }
+
+                // stored answers belong to the puzzle input.
+                if check && (dhat || submit.is_some() || input != InputSource::Puzzle) {
+                    return Err(
+                        "`--check` can not be used with `--dhat`, `--submit` or other inputs."
+                            .into(),
+                    );
+                }
+
+                // answers are submitted for one day at a time, so a set of days could submit by accident.
+                if (submit.is_some() || force) && days.len() != 1 {
+                    return Err(
+                        "`--submit` and `--force` can only be used with a single day.".into(),
+                    );
+                }
+
+                AppArguments::Solve {
+                    days,
+                    release,
+                    checked,
+                    dhat,
+                    check,
+                    submit,
+                    force,
+                    input,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            Some(x) => return Err(format!("Unknown command: {x}").into()),
+            None => return Err("No command specified.".into()),
         };
 
         let remaining = args.finish();
         if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+            return Err(format!("Unknown argument(s): {remaining:?}.").into());
         }
 
         Ok(app_args)
     }
 }
 
-fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+/// Runs a command for each selected day, separating the output of multiple days with a header.
+/// Days that fail do not stop the remaining days. A single failure is returned as is, multiple failures
+/// are combined into one error.
+fn for_each_day(
+    days: &[Day],
+    mut handle: impl FnMut(Day) -> Result<(), Error>,
+) -> Result<(), Error> {
+    if days.is_empty() {
+        println!("No days selected.");
+    }
+
+    let mut failures: Vec<(Day, Error)> = vec![];
+
+    for (i, day) in days.iter().enumerate() {
+        if days.len() > 1 {
+            if i > 0 {
+                println!();
+            }
+            output::reporter().heading(&format!("Day {day}"));
+            output::reporter().heading("------");
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+        if let Err(e) = handle(*day) {
+            failures.push((*day, e));
+        }
+    }
+
+    match failures.len() {
+        0 => Ok(()),
+        1 => Err(failures.remove(0).1),
+        n => {
+            let errors: Vec<String> = failures
+                .iter()
+                .map(|(day, e)| format!("\n  Day {day}: {e}"))
+                .collect();
+            Err(Error::Failed(format!(
+                "{n} of {} days failed:{}",
+                days.len(),
+                errors.concat()
+            )))
+        }
+    }
+}
+
+fn run(args: AppArguments) -> Result<(), Error> {
+    match args {
+        AppArguments::All {
+            days,
+            release,
+            checked,
+            check,
+            timeout,
+            reports,
+        } => all::handle(&days, release, checked, check, timeout, &reports),
+        AppArguments::Time {
+            days,
+            all,
+            store,
+            compare,
+            fail_on_regression,
+            enforce_budget,
+        } => time::handle(
+            days,
+            all,
+            store,
+            compare,
+            fail_on_regression,
+            enforce_budget,
+        ),
+        AppArguments::TimingsHistory { days } => history::handle(&days),
+        AppArguments::Verify { days, release } => verify::handle(&days, release),
+        AppArguments::Robust { days, release, raw } => robust::handle(&days, release, raw),
+        AppArguments::Flaky {
+            days,
+            runs,
+            perturb,
+            release,
+        } => flaky::handle(&days, runs, perturb, release),
+        AppArguments::Download { days } => for_each_day(&days, download::handle),
+        AppArguments::Read { days } => for_each_day(&days, read::handle),
+        AppArguments::Scaffold {
+            days,
+            download,
+            overwrite,
+        } => for_each_day(&days, |day| {
+            scaffold::handle(day, overwrite)?;
+            if download {
+                download::handle(day)?;
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+            Ok(())
+        }),
+        AppArguments::Solve {
+            days,
+            release,
+            checked,
+            dhat,
+            check,
+            submit,
+            force,
+            input,
+        } => {
+            // checking answers is what `all --check` does, so it shares its runs and summary.
+            if check {
+                all::handle(&days, release, checked, true, None, &[])
+            } else {
+                for_each_day(&days, |day| {
+                    solve::handle(day, release, checked, dhat, submit, force, &input)
+                })
+            }
+        }
+        #[cfg(feature = "today")]
+        AppArguments::Today => match Day::today() {
+            Some(day) => {
+                scaffold::handle(day, false)?;
+                download::handle(day)?;
+                read::handle(day)
             }
+            None => Err(Error::Usage(
+                "`today` command can only be run between the 1st and \
+                the 25th of december. Please use `scaffold` with a specific day."
+                    .into(),
+            )),
         },
-    };
+    }
+}
+
+fn main() {
+    // the config is loaded once upfront, so commands can rely on `config::get` not failing.
+    let result = config::init()
+        .map_err(Error::from)
+        .and_then(|_| parse().map_err(|e| Error::Usage(e.to_string())))
+        .and_then(run);
+
+    if let Err(e) = result {
+        eprintln!("Error: {e}");
+        process::exit(e.exit_code());
+    }
 }
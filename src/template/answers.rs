@@ -0,0 +1,146 @@
+/// Known answers for an input, stored next to it in a `.answers` file.
+///
+/// ```text
+/// 1: 1651298
+/// 2: 21306195
+/// ```
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::template::ocr;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    /// Returns the path of the answers file that belongs to an input file, e.g. `alice.answers` for `alice.txt`.
+    pub fn path_for(input_path: &Path) -> PathBuf {
+        input_path.with_extension("answers")
+    }
+
+    /// Reads the answers for an input file. If not present, returns empty answers.
+    pub fn read_for(input_path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(Self::path_for(input_path)) {
+            Ok(s) => s.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Writes the answers next to an input file.
+    pub fn write_for(&self, input_path: &Path) -> Result<(), io::Error> {
+        fs::write(Self::path_for(input_path), self.to_string())
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_1 = Some(answer.into()),
+            2 => self.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+}
+
+/// Returns an answer the way it is stored: letter art is submitted and stored as the letters it depicts, so it is
+/// recognised before comparing it with a known answer. Other answers, and art that cannot be recognised, are unchanged.
+pub fn normalise(answer: &str) -> String {
+    match ocr::recognise(answer) {
+        Ok(letters) if answer.contains('\n') => letters,
+        _ => answer.to_string(),
+    }
+}
+
+impl FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Self::default();
+
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            let (part, answer) = line
+                .split_once(':')
+                .ok_or_else(|| format!("expected `<part>: <answer>`, found `{line}`."))?;
+
+            let answer = answer.trim();
+            if answer.is_empty() || answer == "-" {
+                continue;
+            }
+
+            match part.trim() {
+                "1" => answers.part_1 = Some(answer.into()),
+                "2" => answers.part_2 = Some(answer.into()),
+                x => return Err(format!("expected part to be 1 or 2, found `{x}`.")),
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "1: {}", self.part_1.as_deref().unwrap_or("-"))?;
+        writeln!(f, "2: {}", self.part_2.as_deref().unwrap_or("-"))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{normalise, Answers};
+
+    #[test]
+    fn parses_answers() {
+        let answers: Answers = "1: 42\n2: abc\n".parse().unwrap();
+        assert_eq!(answers.get(1), Some("42"));
+        assert_eq!(answers.get(2), Some("abc"));
+    }
+
+    #[test]
+    fn parses_missing_answers() {
+        let answers: Answers = "1: 42\n2: -\n".parse().unwrap();
+        assert_eq!(answers.get(1), Some("42"));
+        assert_eq!(answers.get(2), None);
+
+        let answers: Answers = "2: 7".parse().unwrap();
+        assert_eq!(answers.get(1), None);
+        assert_eq!(answers.get(2), Some("7"));
+    }
+
+    #[test]
+    fn normalises_letter_art() {
+        let art = ".##.\n#..#\n#..#\n####\n#..#\n#..#";
+        assert_eq!(normalise(art), "A");
+        assert_eq!(normalise("42"), "42");
+        assert_eq!(normalise("#\n."), "#\n.");
+    }
+
+    #[test]
+    fn rejects_malformed_answers() {
+        assert!("42".parse::<Answers>().is_err());
+        assert!("3: 42".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn round_trips_answers() {
+        let mut answers = Answers::default();
+        answers.set(2, "1234");
+        assert_eq!(answers.to_string().parse::<Answers>().unwrap(), answers);
+    }
+}
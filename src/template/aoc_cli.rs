@@ -1,10 +1,11 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    path::Path,
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::{config, profile, submissions::Verdict, Day};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -34,7 +35,7 @@ pub fn check() -> Result<(), AocCommandError> {
 }
 
 pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+    let puzzle_path = path_to_string(&profile::get_puzzle_path(day));
 
     let args = build_args(
         "read",
@@ -50,8 +51,8 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
 }
 
 pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+    let input_path = path_to_string(&profile::get_input_path(day));
+    let puzzle_path = path_to_string(&profile::get_puzzle_path(day));
 
     let args = build_args(
         "download",
@@ -72,37 +73,73 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
+/// Re-downloads the puzzle description only, e.g. to reveal part two after solving part one.
+pub fn download_puzzle(day: Day) -> Result<Output, AocCommandError> {
+    let puzzle_path = path_to_string(&profile::get_puzzle_path(day));
+
+    let args = build_args(
+        "download",
+        &[
+            "--overwrite".into(),
+            "--puzzle-only".into(),
+            "--puzzle-file".into(),
+            puzzle_path.to_string(),
+        ],
+        day,
+    );
+
+    let output = call_aoc_cli(&args)?;
+    println!("🎄 Successfully refreshed puzzle at \"{}\".", &puzzle_path);
+    Ok(output)
+}
+
 pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
-}
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    // capture stdout so callers can inspect the response, but still show it to the user.
+    let output = Command::new("aoc")
+        .args(&args)
+        .current_dir(config::root())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+/// Determines how the AoC server responded to a submission.
+pub fn get_verdict(output: &Output) -> Verdict {
+    Verdict::from_response(&String::from_utf8_lossy(&output.stdout))
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+/// aoc-cli runs in the project root, so paths within it are passed relative to it.
+fn path_to_string(path: &Path) -> String {
+    config::relative(path).to_string_lossy().into()
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
+    if let Some(year) = config::get().year {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
     }
 
+    if let Some(session_file) = profile::get_session_file() {
+        cmd_args.push("--session-file".into());
+        cmd_args.push(path_to_string(&session_file));
+    }
+
     cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
 
     cmd_args
@@ -112,6 +149,7 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
     let output = Command::new("aoc")
         .args(args)
+        .current_dir(config::root())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .output()
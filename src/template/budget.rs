@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use crate::template::config::BudgetConfig;
+use crate::template::output;
+use crate::template::timings::{format_nanos, Timing, Timings};
+
+/// How much of a configured budget a day, a part or all days use.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BudgetUsage {
+    pub name: String,
+    pub nanos: f64,
+    pub budget: Duration,
+}
+
+impl BudgetUsage {
+    /// The used share of the budget, in percent.
+    pub fn percent(&self) -> f64 {
+        let budget = self.budget.as_nanos();
+        if budget == 0 {
+            return if self.nanos > 0.0 { f64::INFINITY } else { 0.0 };
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let budget = budget as f64;
+        self.nanos / budget * 100.0
+    }
+
+    pub fn is_overrun(&self) -> bool {
+        self.percent() > 100.0
+    }
+}
+
+/// Returns the usage of the budgets of a day, its first and its second part, if configured.
+pub fn day_usages(config: &BudgetConfig, timing: &Timing) -> [Option<BudgetUsage>; 3] {
+    let Some(budget) = config.days.get(&timing.day) else {
+        return Default::default();
+    };
+
+    let day = timing.day;
+    let parts = [timing.part_1, timing.part_2];
+
+    let part = |i: usize| {
+        Some(BudgetUsage {
+            name: format!("Day {day} Part {}", i + 1),
+            nanos: parts[i]?.nanos,
+            budget: budget.parts[i]?,
+        })
+    };
+
+    [
+        budget.total.map(|total| BudgetUsage {
+            name: format!("Day {day}"),
+            nanos: timing.total_nanos,
+            budget: total,
+        }),
+        part(0),
+        part(1),
+    ]
+}
+
+/// Returns the usage of the total budget by all timings, if configured.
+pub fn total_usage(config: &BudgetConfig, timings: &Timings) -> Option<BudgetUsage> {
+    Some(BudgetUsage {
+        name: "Total".into(),
+        nanos: timings.data.iter().map(|x| x.total_nanos).sum(),
+        budget: config.total?,
+    })
+}
+
+/// Returns the usage of the budgets of all days and their parts, followed by the usage of the total budget.
+pub fn usages(config: &BudgetConfig, timings: &Timings) -> Vec<BudgetUsage> {
+    timings
+        .data
+        .iter()
+        .flat_map(|timing| day_usages(config, timing))
+        .chain([total_usage(config, timings)])
+        .flatten()
+        .collect()
+}
+
+/// Formats how much of a budget is used, e.g. `62% of 50ms`.
+pub fn format_usage(usage: &BudgetUsage) -> String {
+    format!("{:.0}% of {:?}", usage.percent(), usage.budget)
+}
+
+/// Prints the usage of budgets as a table, marking overruns.
+pub fn print(usages: &[BudgetUsage]) {
+    println!("\n{}", output::reporter().strong("Budget"));
+
+    let header: Vec<String> = ["", "Time", "Budget", "Usage", ""]
+        .map(String::from)
+        .to_vec();
+
+    let rows: Vec<Vec<String>> = usages
+        .iter()
+        .map(|usage| {
+            let marker = if usage.is_overrun() {
+                "⚠ over budget"
+            } else {
+                ""
+            };
+
+            vec![
+                usage.name.clone(),
+                format_nanos(usage.nanos),
+                format!("{:?}", usage.budget),
+                format!("{:.1}%", usage.percent()),
+                marker.into(),
+            ]
+        })
+        .collect();
+
+    output::print_table(&header, &rows);
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::BTreeMap, time::Duration};
+
+    use super::{format_usage, usages};
+    use crate::{
+        day,
+        template::config::{BudgetConfig, DayBudget},
+        template::timings::{PartTiming, Timing, Timings},
+        template::Day,
+    };
+
+    fn timing(day: Day, part_1: f64, part_2: f64) -> Timing {
+        let part = |millis: f64| PartTiming {
+            nanos: millis * 1_000_000.0,
+            samples: None,
+            std_dev: None,
+        };
+
+        Timing {
+            day,
+            part_1: Some(part(part_1)),
+            part_2: Some(part(part_2)),
+            total_nanos: (part_1 + part_2) * 1_000_000.0,
+            status: Default::default(),
+        }
+    }
+
+    #[test]
+    fn computes_usage_of_budgets() {
+        let config = BudgetConfig {
+            total: Some(Duration::from_millis(100)),
+            days: BTreeMap::from([
+                (
+                    day!(1),
+                    DayBudget {
+                        total: Some(Duration::from_millis(50)),
+                        parts: [None, Some(Duration::from_millis(10))],
+                    },
+                ),
+                (day!(3), DayBudget::default()),
+            ]),
+            show_in_readme: false,
+        };
+        let timings = Timings {
+            data: vec![timing(day!(1), 20.0, 11.0), timing(day!(4), 40.0, 40.0)],
+        };
+
+        let usages = usages(&config, &timings);
+        let names: Vec<&str> = usages.iter().map(|x| x.name.as_str()).collect();
+        assert_eq!(names, vec!["Day 01", "Day 01 Part 2", "Total"]);
+
+        assert_eq!(format_usage(&usages[0]), "62% of 50ms");
+        assert!(!usages[0].is_overrun());
+        assert!(usages[1].is_overrun());
+        assert!((usages[2].percent() - 111.0).abs() < 1e-9);
+        assert!(usages[2].is_overrun());
+    }
+}
@@ -1,5 +1,46 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    report::{self, ReportTarget},
+    run_multi::{child_commands::BuildProfile, run_multi},
+    Day, Error,
+};
+
+pub fn handle(
+    days: &[Day],
+    is_release: bool,
+    with_overflow_checks: bool,
+    is_checked: bool,
+    timeout: Option<Duration>,
+    reports: &[ReportTarget],
+) -> Result<(), Error> {
+    let runs = run_multi(
+        &days.iter().copied().collect(),
+        BuildProfile::from_flags(is_release, with_overflow_checks),
+        false,
+        is_checked,
+        timeout,
+    );
+
+    if !reports.is_empty() {
+        let cases = report::collect_test_cases(&runs);
+
+        for target in reports {
+            report::write(target, &cases).map_err(|e| {
+                Error::io(format!("write report to \"{}\"", target.path.display()), e)
+            })?;
+            println!("Wrote report to \"{}\".", target.path.display());
+        }
+    }
+
+    // a failing day fails the command, so it can be used e.g. as a pre-push check.
+    let failed = runs.iter().filter(|run| run.status.is_failure()).count();
+    if failed > 0 {
+        return Err(Error::Failed(format!(
+            "{failed} of {} days failed.",
+            runs.len()
+        )));
+    }
+
+    Ok(())
 }
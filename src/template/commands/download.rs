@@ -1,14 +1,18 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{aoc_cli, profile, Day, Error};
+use std::fs;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+pub fn handle(day: Day) -> Result<(), Error> {
+    aoc_cli::check()?;
+
+    // profile paths are nested in per-day directories that might not exist yet.
+    for path in [profile::get_input_path(day), profile::get_puzzle_path(day)] {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                Error::io(format!("create directory for \"{}\"", path.display()), e)
+            })?;
+        }
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    aoc_cli::download(day)?;
+    Ok(())
 }
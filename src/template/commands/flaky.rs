@@ -0,0 +1,208 @@
+use std::thread;
+
+use crate::template::input::InputSource;
+use crate::template::run_multi::child_commands::{
+    parse_part_outputs, run_solution_with_env, SolutionOutput,
+};
+use crate::template::runner::PERTURB_ENV;
+use crate::template::stats::Stats;
+use crate::template::timings::{format_nanos, parse_nanos};
+use crate::template::{output, Day, Error};
+
+/// Timings varying more than this relative to their mean are highlighted.
+const HIGH_VARIANCE: f64 = 0.25;
+
+/// What a part returned in each run, and how long it took.
+#[derive(Default)]
+struct PartRuns {
+    outcomes: Vec<String>,
+    nanos: Vec<f64>,
+}
+
+impl PartRuns {
+    /// Returns the distinct outcomes with how often they occurred, in order of their first occurrence.
+    fn distinct_outcomes(&self) -> Vec<(&str, usize)> {
+        let mut distinct: Vec<(&str, usize)> = vec![];
+        for outcome in &self.outcomes {
+            match distinct.iter_mut().find(|(x, _)| x == outcome) {
+                Some((_, count)) => *count += 1,
+                None => distinct.push((outcome, 1)),
+            }
+        }
+        distinct
+    }
+}
+
+/// Returns what a part printed in a run: its answer, its error or why the solution bin crashed.
+/// `None` if the part is not implemented.
+fn get_outcome(part: u8, output: &SolutionOutput) -> Option<String> {
+    let parts = parse_part_outputs(&output.stdout);
+
+    let Some(result) = parts.iter().find(|p| p.part == part) else {
+        return (!output.success).then(|| output.panic_message());
+    };
+
+    match (&result.answer, &result.error) {
+        (Some(answer), _) => Some(answer.clone()),
+        (None, Some(error)) => Some(format!("✖ {error}")),
+        (None, None) => None,
+    }
+}
+
+/// Environment variables of a perturbed run. The runs alternate between different numbers of busy threads
+/// competing with the solution for CPU time, which are started by the solution's runner.
+fn perturbation(run: usize) -> Vec<(&'static str, String)> {
+    let cpus = thread::available_parallelism().map_or(1, |x| x.get());
+    vec![(PERTURB_ENV, (run % (cpus + 1)).to_string())]
+}
+
+fn format_timings(nanos: &[f64]) -> String {
+    let Some(stats) = Stats::of(nanos) else {
+        return String::new();
+    };
+
+    let timings = format!(
+        "({} ± {}, {}–{})",
+        format_nanos(stats.mean),
+        format_nanos(stats.std_dev),
+        format_nanos(stats.min),
+        format_nanos(stats.max)
+    );
+
+    if stats.coefficient_of_variation() > HIGH_VARIANCE {
+        format!(
+            " {} {}",
+            output::reporter().subtle(&timings),
+            output::reporter().strong("⚠ high timing variance")
+        )
+    } else {
+        format!(" {}", output::reporter().subtle(&timings))
+    }
+}
+
+pub fn handle(days: &[Day], runs: usize, perturb: bool, is_release: bool) -> Result<(), Error> {
+    if runs < 2 {
+        return Err(Error::Usage(
+            "`--runs` has to be at least 2 to compare runs.".into(),
+        ));
+    }
+
+    let mut flaky = 0;
+    let mut total = 0;
+
+    for (i, day) in days.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        output::reporter().heading(&format!("Day {day}"));
+        output::reporter().heading("------");
+
+        let mut parts: [PartRuns; 2] = Default::default();
+
+        for run in 0..runs {
+            let envs = if perturb { perturbation(run) } else { vec![] };
+            let output = run_solution_with_env(*day, is_release, &InputSource::Puzzle, &envs)?;
+            let durations = parse_part_outputs(&output.stdout);
+
+            for (part, runs) in (1..=2).zip(parts.iter_mut()) {
+                let Some(outcome) = get_outcome(part, &output) else {
+                    continue;
+                };
+                runs.outcomes.push(outcome);
+
+                let nanos = durations
+                    .iter()
+                    .find(|p| p.part == part)
+                    .and_then(|p| parse_nanos(p.duration.as_deref()?));
+                runs.nanos.extend(nanos);
+            }
+        }
+
+        for (part, runs) in (1..=2).zip(parts.iter()) {
+            if runs.outcomes.is_empty() {
+                continue;
+            }
+            total += 1;
+
+            let distinct = runs.distinct_outcomes();
+            let timings = format_timings(&runs.nanos);
+
+            if let [(outcome, _)] = distinct[..] {
+                println!(
+                    "Part {part}: ✔ {} in {runs} runs{timings}",
+                    output::reporter().strong(outcome),
+                    runs = runs.outcomes.len()
+                );
+                continue;
+            }
+
+            flaky += 1;
+            println!(
+                "Part {part}: ✖ {} different results in {} runs{timings}",
+                distinct.len(),
+                runs.outcomes.len()
+            );
+            for (outcome, count) in distinct {
+                println!("  {count}× {outcome}");
+            }
+        }
+    }
+
+    if total == 0 {
+        return Err(Error::Failed("No parts returned an answer.".into()));
+    }
+
+    if flaky > 0 {
+        return Err(Error::Failed(format!(
+            "{flaky} of {total} parts returned different results across runs."
+        )));
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{get_outcome, PartRuns};
+    use crate::template::run_multi::child_commands::SolutionOutput;
+
+    fn solution_output(stdout: &[&str], stderr: &[&str], success: bool) -> SolutionOutput {
+        SolutionOutput {
+            stdout: stdout.iter().map(|x| x.to_string()).collect(),
+            stderr: stderr.iter().map(|x| x.to_string()).collect(),
+            success,
+            timed_out: false,
+        }
+    }
+
+    #[test]
+    fn counts_distinct_outcomes() {
+        let runs = PartRuns {
+            outcomes: vec!["17".into(), "18".into(), "17".into()],
+            nanos: vec![],
+        };
+        assert_eq!(runs.distinct_outcomes(), vec![("17", 2), ("18", 1)]);
+    }
+
+    #[test]
+    fn gets_outcomes_of_parts() {
+        let output = solution_output(&["Part 1: 42 (1.2ms)", "Part 2: ✖"], &[], true);
+        assert_eq!(get_outcome(1, &output), Some("42".into()));
+        assert_eq!(get_outcome(2, &output), None);
+
+        let crashed = solution_output(
+            &["Part 1: 42 (1.2ms)"],
+            &[
+                "thread 'main' panicked at src/bin/01.rs:3:5:",
+                "attempt to add with overflow",
+            ],
+            false,
+        );
+        assert_eq!(
+            get_outcome(2, &crashed),
+            Some("panicked at src/bin/01.rs:3:5: attempt to add with overflow".into())
+        );
+    }
+}
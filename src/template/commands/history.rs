@@ -0,0 +1,129 @@
+use crate::template::history::{format_timestamp, Environment, History};
+use crate::template::timings::{format_nanos, PartTiming};
+use crate::template::{config, output, Day, Error};
+
+/// Placeholder for metadata that is unknown, e.g. of runs migrated from files without a history.
+const UNKNOWN: &str = "-";
+
+fn format_part(part: Option<&PartTiming>) -> String {
+    match part {
+        Some(PartTiming {
+            nanos,
+            std_dev: Some(std_dev),
+            ..
+        }) => format!("{} ± {}", format_nanos(*nanos), format_nanos(*std_dev)),
+        Some(part) => part.to_string(),
+        None => UNKNOWN.into(),
+    }
+}
+
+/// Formats the abbreviated commit of a run, marked with `*` if the working tree had uncommitted changes.
+fn format_commit(environment: &Environment) -> String {
+    let Some(commit) = &environment.commit else {
+        return UNKNOWN.into();
+    };
+
+    let short: String = commit.chars().take(7).collect();
+    if environment.dirty == Some(true) {
+        format!("{short}*")
+    } else {
+        short
+    }
+}
+
+/// Shortens a version like `rustc 1.83.0 (90b35a623 2024-11-26)` to `1.83.0`.
+fn format_rustc(rustc: &str) -> String {
+    rustc.split_whitespace().nth(1).unwrap_or(rustc).to_string()
+}
+
+fn format_row(
+    environment: &Environment,
+    part_1: Option<&PartTiming>,
+    part_2: Option<&PartTiming>,
+) -> Vec<String> {
+    let or_unknown = |x: Option<String>| x.unwrap_or_else(|| UNKNOWN.into());
+
+    vec![
+        or_unknown(environment.timestamp.map(format_timestamp)),
+        format_commit(environment),
+        or_unknown(environment.profile.clone()),
+        or_unknown(environment.rustc.as_deref().map(format_rustc)),
+        or_unknown(environment.cpu.clone()),
+        or_unknown(environment.hostname.clone()),
+        format_part(part_1),
+        format_part(part_2),
+    ]
+}
+
+pub fn handle(days: &[Day]) -> Result<(), Error> {
+    let history = History::read_from_file().map_err(|e| {
+        Error::Failed(format!(
+            "Could not read \"{}\": {e}",
+            config::relative(&config::get().timings_path()).display()
+        ))
+    })?;
+
+    let header: Vec<String> = [
+        "Date", "Commit", "Profile", "rustc", "CPU", "Host", "Part 1", "Part 2",
+    ]
+    .map(String::from)
+    .to_vec();
+
+    for (i, day) in days.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        output::reporter().heading(&format!("Day {day}"));
+        output::reporter().heading("------");
+
+        let rows: Vec<Vec<String>> = history
+            .for_day(*day)
+            .into_iter()
+            .map(|(environment, timing)| {
+                format_row(environment, timing.part_1.as_ref(), timing.part_2.as_ref())
+            })
+            .collect();
+
+        if rows.is_empty() {
+            println!("No stored timings. Run `cargo time {day} --store` to store some.");
+        } else {
+            output::print_table(&header, &rows);
+        }
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_commit, format_row, format_rustc};
+    use crate::template::history::Environment;
+
+    #[test]
+    fn formats_commits() {
+        let mut environment = Environment {
+            commit: Some("0123456789abcdef".into()),
+            dirty: Some(false),
+            ..Environment::default()
+        };
+        assert_eq!(format_commit(&environment), "0123456");
+
+        environment.dirty = Some(true);
+        assert_eq!(format_commit(&environment), "0123456*");
+        assert_eq!(format_commit(&Environment::default()), "-");
+    }
+
+    #[test]
+    fn formats_unknown_environments() {
+        assert_eq!(
+            format_row(&Environment::default(), None, None),
+            vec!["-"; 8]
+        );
+        assert_eq!(
+            format_rustc("rustc 1.83.0 (90b35a623 2024-11-26)"),
+            "1.83.0"
+        );
+    }
+}
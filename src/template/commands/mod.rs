@@ -1,6 +1,10 @@
 pub mod all;
 pub mod download;
+pub mod flaky;
+pub mod history;
 pub mod read;
+pub mod robust;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+pub mod verify;
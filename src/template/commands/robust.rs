@@ -0,0 +1,274 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::input::{InputSource, RAW_INPUT_ENV};
+use crate::template::run_multi::child_commands::{
+    parse_part_outputs, run_solution_with_env, SolutionOutput,
+};
+use crate::template::timings::PartStatus;
+use crate::template::{config, output, profile, Day, Error, Normalize};
+
+/// A change to an input that should not affect the answers of a solution.
+struct Mutation {
+    name: &'static str,
+    apply: fn(&str) -> String,
+}
+
+const MUTATIONS: [Mutation; 4] = [
+    Mutation {
+        name: "CRLF line endings",
+        apply: crlf_line_endings,
+    },
+    Mutation {
+        name: "no trailing newline",
+        apply: no_trailing_newline,
+    },
+    Mutation {
+        name: "extra blank line at the end",
+        apply: extra_blank_line,
+    },
+    Mutation {
+        name: "trailing spaces",
+        apply: trailing_spaces,
+    },
+];
+
+fn crlf_line_endings(input: &str) -> String {
+    input.replace("\r\n", "\n").replace('\n', "\r\n")
+}
+
+fn no_trailing_newline(input: &str) -> String {
+    input.trim_end_matches(['\r', '\n']).to_string()
+}
+
+fn extra_blank_line(input: &str) -> String {
+    no_trailing_newline(input) + "\n\n"
+}
+
+fn trailing_spaces(input: &str) -> String {
+    input
+        .split_inclusive('\n')
+        .map(|line| {
+            let content = line.trim_end_matches(['\r', '\n']);
+            format!("{content} {}", &line[content.len()..])
+        })
+        .collect()
+}
+
+/// How a part behaved on a mutated input.
+enum Outcome {
+    Robust,
+    Changed(String),
+    Panicked(String),
+}
+
+#[derive(Default)]
+struct Summary {
+    robust: usize,
+    fragile: usize,
+}
+
+/// Returns the puzzle input and all examples of a day that exist and are not empty.
+fn collect_inputs(day: Day) -> Vec<(String, PathBuf)> {
+    let mut inputs = vec![("puzzle input".to_string(), profile::get_input_path(day))];
+
+    let examples_dir = config::get().data_dir.join("examples");
+    let mut examples: Vec<PathBuf> = fs::read_dir(examples_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name == format!("{day}.txt")
+                || (name.starts_with(&format!("{day}-")) && name.ends_with(".txt"))
+        })
+        .collect();
+    examples.sort();
+
+    inputs.extend(examples.into_iter().map(|path| {
+        let name = config::relative(&path).display().to_string();
+        (name, path)
+    }));
+
+    inputs.retain(|(_, path)| fs::metadata(path).is_ok_and(|m| m.len() > 0));
+    inputs
+}
+
+fn get_outcome(expected: &str, part: u8, output: &SolutionOutput) -> Outcome {
+    let parts = parse_part_outputs(&output.stdout);
+
+    let Some(result) = parts.iter().find(|p| p.part == part) else {
+        return Outcome::Panicked(output.panic_message());
+    };
+
+    match (&result.answer, &result.error) {
+        (Some(answer), _) if answer == expected => Outcome::Robust,
+        (Some(answer), _) => Outcome::Changed(format!("{expected} became {answer}")),
+        (None, Some(error)) if result.status() == PartStatus::Panicked => {
+            Outcome::Panicked(error.clone())
+        }
+        (None, Some(error)) => Outcome::Changed(format!("{expected} became error: {error}")),
+        (None, None) => Outcome::Changed(format!("{expected} became ✖")),
+    }
+}
+
+/// Runs the solutions of `days` against mutated inputs. Inputs are normalised as configured, unless `is_raw`
+/// is set, which passes them to the solution unchanged.
+pub fn handle(days: &[Day], is_release: bool, is_raw: bool) -> Result<(), Error> {
+    let dir = env::temp_dir().join(format!("aoc-robust-{}", process::id()));
+    fs::create_dir_all(&dir)
+        .map_err(|e| Error::io(format!("create directory \"{}\"", dir.display()), e))?;
+
+    let result = run_days(days, is_release, is_raw, &dir);
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+fn run_days(days: &[Day], is_release: bool, is_raw: bool, dir: &Path) -> Result<(), Error> {
+    let mut summary = Summary::default();
+    let mut tested_days = 0;
+
+    if is_raw {
+        println!("Passing inputs and their variants to the solutions without normalising them.");
+        println!();
+    }
+
+    for day in days {
+        let inputs = collect_inputs(*day);
+
+        if inputs.is_empty() {
+            eprintln!(
+                "No inputs found for day {day}. Run `cargo download {day}` or add an example."
+            );
+            continue;
+        }
+
+        if tested_days > 0 {
+            println!();
+        }
+        tested_days += 1;
+        run_day(*day, &inputs, is_release, is_raw, dir, &mut summary)?;
+    }
+
+    if tested_days == 0 {
+        return Err(Error::Failed("No inputs to mutate.".into()));
+    }
+
+    println!("------");
+    let totals = format!("{} robust, {} fragile.", summary.robust, summary.fragile);
+    println!("{}", output::reporter().strong(&totals));
+
+    if summary.fragile > 0 {
+        return Err(Error::Failed(format!(
+            "{} of {} variants changed an answer or panicked.",
+            summary.fragile,
+            summary.robust + summary.fragile
+        )));
+    }
+
+    Ok(())
+}
+
+fn run_day(
+    day: Day,
+    inputs: &[(String, PathBuf)],
+    is_release: bool,
+    is_raw: bool,
+    dir: &Path,
+    summary: &mut Summary,
+) -> Result<(), Error> {
+    output::reporter().heading(&format!("Day {day}"));
+    output::reporter().heading("------");
+
+    let envs = if is_raw {
+        vec![(RAW_INPUT_ENV, "1".to_string())]
+    } else {
+        vec![]
+    };
+
+    for (name, path) in inputs {
+        println!("{name}");
+
+        // variants are made from the file as it is, like a copy saved by another editor.
+        let source = InputSource::File(path.clone());
+        let Some(input) = source
+            .read_bytes(day, Normalize::RAW)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+        else {
+            eprintln!("  Could not read \"{}\" as text.", path.display());
+            continue;
+        };
+
+        let baseline = run_solution_with_env(day, is_release, &source, &envs)?;
+        let expected: Vec<(u8, String)> = parse_part_outputs(&baseline.stdout)
+            .into_iter()
+            .filter_map(|p| Some((p.part, p.answer?)))
+            .collect();
+
+        if expected.is_empty() {
+            println!("  ? no answers to compare against");
+            continue;
+        }
+
+        for (i, mutation) in MUTATIONS.iter().enumerate() {
+            let mutated = (mutation.apply)(&input);
+            if mutated == input {
+                continue;
+            }
+
+            let mutated_path = dir.join(format!("{day}-{i}.txt"));
+            fs::write(&mutated_path, mutated)
+                .map_err(|e| Error::io(format!("write \"{}\"", mutated_path.display()), e))?;
+
+            let output =
+                run_solution_with_env(day, is_release, &InputSource::File(mutated_path), &envs)?;
+
+            let problems: Vec<String> = expected
+                .iter()
+                .filter_map(|(part, answer)| match get_outcome(answer, *part, &output) {
+                    Outcome::Robust => None,
+                    Outcome::Changed(change) => Some(format!("Part {part}: {change}")),
+                    Outcome::Panicked(message) => Some(format!("Part {part}: {message}")),
+                })
+                .collect();
+
+            if problems.is_empty() {
+                summary.robust += 1;
+                println!("  ✔ {}", mutation.name);
+            } else {
+                summary.fragile += 1;
+                println!("  ✖ {}", mutation.name);
+                for problem in problems {
+                    println!("    {problem}");
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{crlf_line_endings, extra_blank_line, no_trailing_newline, trailing_spaces};
+
+    #[test]
+    fn mutates_line_endings() {
+        assert_eq!(crlf_line_endings("1 2\n3 4\n"), "1 2\r\n3 4\r\n");
+        assert_eq!(crlf_line_endings("1 2\r\n3 4"), "1 2\r\n3 4");
+        assert_eq!(no_trailing_newline("1 2\n3 4\n\n"), "1 2\n3 4");
+        assert_eq!(extra_blank_line("1 2\n3 4"), "1 2\n3 4\n\n");
+    }
+
+    #[test]
+    fn appends_trailing_spaces() {
+        assert_eq!(trailing_spaces("1 2\r\n3 4\n5"), "1 2 \r\n3 4 \n5 ");
+        assert_eq!(trailing_spaces(""), "");
+    }
+}
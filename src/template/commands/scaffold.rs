@@ -1,15 +1,15 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
-    process,
+    path::Path,
 };
 
-use crate::template::Day;
+use crate::template::{config, profile, Day, Error};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,7 +19,11 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,53 +31,82 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(day: Day, overwrite: bool) -> Result<(), Error> {
+    let input_path = profile::get_input_path(day);
+    let example_path = config::get().data_path("examples", &format!("{day}.txt"));
+    let module_path = config::bin_path(day);
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
+    let template = match &config::get().template {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| Error::io(format!("read template \"{}\"", path.display()), e))?,
+        None => MODULE_TEMPLATE.to_string(),
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
+    let mut file = safe_create_file(&module_path, overwrite).map_err(|e| {
+        Error::io(
+            format!(
+                "create module file \"{}\"",
+                config::relative(&module_path).display()
+            ),
+            e,
+        )
+    })?;
+
+    file.write_all(
+        template
             .replace("%DAY_NUMBER%", &day.into_inner().to_string())
             .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
-    }
+    )
+    .map_err(|e| {
+        Error::io(
+            format!(
+                "write module file \"{}\"",
+                config::relative(&module_path).display()
+            ),
+            e,
+        )
+    })?;
+    println!(
+        "Created module file \"{}\"",
+        config::relative(&module_path).display()
+    );
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    // inputs of profiles are part of the corpus of `cargo verify`, so they are only created by downloading them.
+    if profile::active_profile().is_some() {
+        println!(
+            "Skipped the input file of the profile, run `cargo download {day}` to download it."
+        );
+    } else {
+        create_file(&input_path).map_err(|e| {
+            Error::io(
+                format!(
+                    "create input file \"{}\"",
+                    config::relative(&input_path).display()
+                ),
+                e,
+            )
+        })?;
+        println!(
+            "Created empty input file \"{}\"",
+            config::relative(&input_path).display()
+        );
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
-    }
+    create_file(&example_path).map_err(|e| {
+        Error::io(
+            format!(
+                "create example file \"{}\"",
+                config::relative(&example_path).display()
+            ),
+            e,
+        )
+    })?;
+    println!(
+        "Created empty example file \"{}\"",
+        config::relative(&example_path).display()
+    );
 
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
+    Ok(())
 }
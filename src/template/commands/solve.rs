@@ -1,8 +1,19 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::run_multi::child_commands::BuildProfile;
+use crate::template::{
+    input::InputSource, output, solution_args::SolutionArgs, submissions::SubmitTarget, Day, Error,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    with_overflow_checks: bool,
+    dhat: bool,
+    submit_target: Option<SubmitTarget>,
+    force: bool,
+    input: &InputSource,
+) -> Result<(), Error> {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -12,23 +23,36 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        cmd_args.extend(BuildProfile::from_flags(release, with_overflow_checks).to_args());
     }
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
-    }
+    let child_args = SolutionArgs {
+        submit: submit_target,
+        force,
+        input: input.clone(),
+        format: Some(output::format()),
+        ..Default::default()
+    };
+    cmd_args.extend(child_args.to_args());
 
-    let mut cmd = Command::new("cargo")
+    let status = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status()
+        .map_err(|e| Error::io(format!("run the solution of day {day}"), e))?;
 
-    cmd.wait().unwrap();
+    match status.code() {
+        Some(0) => Ok(()),
+        // solution bins exit with 2 on invalid arguments, configs or profiles, like the CLI.
+        Some(2) => Err(Error::Usage(format!(
+            "The solution of day {day} exited with {status}."
+        ))),
+        _ => Err(Error::Failed(format!(
+            "The solution of day {day} exited with {status}."
+        ))),
+    }
 }
@@ -1,15 +1,52 @@
-use std::collections::HashSet;
+use crate::template::budget;
+use crate::template::compare::{self, Comparison};
+use crate::template::history::{Environment, History};
+use crate::template::run_multi::{child_commands::BuildProfile, run_multi};
+use crate::template::timings::{Timing, Timings};
+use crate::template::{all_days, config, readme_benchmarks, Day, Error};
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+/// Benches the selected days. With `compare`, shows how their timings changed compared to the stored timings,
+/// failing if a part got slower by more than `fail_on_regression` percent. If budgets are configured, shows how much
+/// of them is used, failing on overruns with `enforce_budget`.
+pub fn handle(
+    days: Option<Vec<Day>>,
+    run_all: bool,
+    store: bool,
+    compare: bool,
+    fail_on_regression: Option<f64>,
+    enforce_budget: bool,
+) -> Result<(), Error> {
+    let budget_config = &config::get().budget;
+    if enforce_budget && budget_config.is_empty() {
+        return Err(Error::Usage(format!(
+            "`--enforce-budget` requires a `[budget]` in {}.",
+            config::CONFIG_FILE
+        )));
+    }
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+    // stored timings are compared, checked against budgets, skipped in the incremental mode and kept when
+    // storing. A history that can not be read fails these instead of being treated as empty or overwritten.
+    let needs_history = store
+        || compare
+        || fail_on_regression.is_some()
+        || !budget_config.is_empty()
+        || (days.is_none() && !run_all);
+    let mut history = if needs_history {
+        History::read_from_file().map_err(|e| {
+            Error::Failed(format!(
+                "Could not read \"{}\": {e}",
+                config::relative(&config::get().timings_path()).display()
+            ))
+        })?
+    } else {
+        History::default()
+    };
+    let stored_timings = history.latest();
 
-    let days_to_run = day.map_or_else(
+    let days_to_run = days.map_or_else(
         || {
-            if run_all {
+            // comparing needs stored timings, which the incremental mode would skip.
+            if run_all || compare || fail_on_regression.is_some() {
                 all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
@@ -18,17 +55,65 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |days| days.into_iter().collect(),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let profile = BuildProfile::Release;
+    let runs = run_multi(&days_to_run, profile, true, false, None);
+    let timings = Timings {
+        data: runs.into_iter().filter_map(|run| run.timing).collect(),
+    };
+
+    // the timings of days that panicked are incomplete, so they are neither compared nor stored.
+    let (panicked, complete): (Vec<_>, Vec<_>) =
+        timings.data.into_iter().partition(Timing::is_panicked);
+    let complete = Timings { data: complete };
+
+    let mut failures: Vec<String> = vec![];
+
+    if compare || fail_on_regression.is_some() {
+        let comparisons = Comparison::of(&stored_timings, &complete);
+        compare::print(&comparisons, fail_on_regression);
+
+        if let Some(threshold) = fail_on_regression {
+            let regressions = comparisons
+                .iter()
+                .filter(|comparison| comparison.is_regression(threshold))
+                .count();
+
+            if regressions > 0 {
+                failures.push(format!(
+                    "{regressions} of {} parts got slower by more than {threshold}%.",
+                    comparisons.len()
+                ));
+            }
+        }
+    }
+
+    if !budget_config.is_empty() {
+        // budgets apply to all days, including the stored timings of days that were not benched again.
+        let usages = budget::usages(budget_config, &stored_timings.merge(&complete));
+        budget::print(&usages);
+
+        let overruns = usages.iter().filter(|usage| usage.is_overrun()).count();
+        if enforce_budget && overruns > 0 {
+            failures.push(format!("{overruns} of {} budgets exceeded.", usages.len()));
+        }
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        // keep the stored timings of days that panicked.
+        for timing in &panicked {
+            eprintln!("Day {} panicked, skipped storing its timings.", timing.day);
+        }
+
+        history.push(Environment::detect(profile.name()), complete);
+        history
+            .store_file()
+            .map_err(|e| Error::io("store timings", e))?;
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(history.latest()) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +122,10 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if !failures.is_empty() {
+        return Err(Error::Failed(failures.join(" ")));
+    }
+
+    Ok(())
 }
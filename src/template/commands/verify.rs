@@ -0,0 +1,259 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::template::answers::{self, Answers};
+use crate::template::input::InputSource;
+use crate::template::run_multi::child_commands::{
+    parse_part_outputs, run_solution_with_input, PartOutput, SolutionOutput,
+};
+use crate::template::timings::PartStatus;
+use crate::template::{config, output, Day, Error};
+
+/// The outcome of running one part against one input of the corpus.
+enum Verdict {
+    Pass,
+    Fail,
+    Unverified,
+    Panicked(String),
+}
+
+#[derive(Default)]
+struct Summary {
+    passed: usize,
+    failed: usize,
+    panicked: usize,
+    unverified: usize,
+}
+
+fn get_corpus_dir(day: Day) -> PathBuf {
+    config::get().data_dir.join("inputs").join(day.to_string())
+}
+
+/// Returns the inputs of a corpus directory, skipping empty files that were never filled with an input.
+fn collect_inputs(dir: &Path) -> Result<Vec<PathBuf>, std::io::Error> {
+    let mut inputs: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .filter(|path| fs::metadata(path).is_ok_and(|m| m.is_file() && m.len() > 0))
+        .collect();
+
+    inputs.sort();
+    Ok(inputs)
+}
+
+fn get_verdict(
+    result: Option<&PartOutput>,
+    expected: Option<&str>,
+    output: &SolutionOutput,
+) -> Option<Verdict> {
+    let Some(result) = result else {
+        // parts that did not print a result were either skipped or the solution bin crashed before reaching them.
+        return if output.success {
+            None
+        } else {
+            Some(Verdict::Panicked(output.panic_message()))
+        };
+    };
+
+    if result.status() == PartStatus::Panicked {
+        return Some(Verdict::Panicked(result.error.clone().unwrap_or_default()));
+    }
+
+    let verdict = match (expected, &result.answer) {
+        (Some(expected), Some(answer)) if answers::normalise(answer) == expected => Verdict::Pass,
+        (Some(_), _) => Verdict::Fail,
+        (None, _) => Verdict::Unverified,
+    };
+
+    Some(verdict)
+}
+
+fn format_answer(answer: &str) -> String {
+    if answer.contains('\n') {
+        "▼".into()
+    } else {
+        output::reporter().strong(answer)
+    }
+}
+
+pub fn handle(days: &[Day], is_release: bool) -> Result<(), Error> {
+    let mut summary = Summary::default();
+    let mut verified_days = 0;
+
+    for day in days {
+        let corpus_dir = get_corpus_dir(*day);
+
+        match collect_inputs(&corpus_dir) {
+            Ok(inputs) if !inputs.is_empty() => {
+                if verified_days > 0 {
+                    println!();
+                }
+                verified_days += 1;
+                verify_day(*day, &inputs, is_release, &mut summary)?;
+            }
+            _ => eprintln!(
+                "No inputs found in \"{}\". Add one `<name>.txt` file per input and its known answers to `<name>.answers`.",
+                corpus_dir.display()
+            ),
+        }
+    }
+
+    if verified_days == 0 {
+        return Err(Error::Failed("No inputs to verify.".into()));
+    }
+
+    println!("------");
+    let totals = format!(
+        "{} passed, {} failed, {} panicked, {} unverified.",
+        summary.passed, summary.failed, summary.panicked, summary.unverified
+    );
+    println!("{}", output::reporter().strong(&totals));
+
+    if summary.failed > 0 || summary.panicked > 0 {
+        return Err(Error::Failed(format!(
+            "{} of {} parts failed.",
+            summary.failed + summary.panicked,
+            summary.passed + summary.failed + summary.panicked + summary.unverified
+        )));
+    }
+
+    Ok(())
+}
+
+fn verify_day(
+    day: Day,
+    inputs: &[PathBuf],
+    is_release: bool,
+    summary: &mut Summary,
+) -> Result<(), Error> {
+    output::reporter().heading(&format!("Day {day}"));
+    output::reporter().heading("------");
+
+    for input in inputs {
+        let name = input.file_name().unwrap_or_default().to_string_lossy();
+        println!("{name}");
+
+        let answers = Answers::read_for(input).unwrap_or_else(|e| {
+            eprintln!("  Could not parse answers: {e}");
+            Answers::default()
+        });
+
+        let output = run_solution_with_input(day, is_release, &InputSource::File(input.clone()))?;
+
+        let parts = parse_part_outputs(&output.stdout);
+
+        for part in 1..=2 {
+            let result = parts.iter().find(|p| p.part == part);
+
+            let Some(verdict) = get_verdict(result, answers.get(part), &output) else {
+                continue;
+            };
+
+            let answer = result.map_or_else(String::new, |p| match (&p.answer, &p.error) {
+                (Some(answer), _) => format_answer(answer),
+                (None, Some(error)) => format!("error: {error}"),
+                (None, None) => "✖".into(),
+            });
+
+            let duration = result
+                .and_then(|p| p.duration.as_deref())
+                .map(|d| format!(" {}", output::reporter().subtle(&format!("({d})"))))
+                .unwrap_or_default();
+
+            let line = match verdict {
+                Verdict::Pass => {
+                    summary.passed += 1;
+                    format!("✔ {answer}{duration}")
+                }
+                Verdict::Fail => {
+                    summary.failed += 1;
+                    let expected = format_answer(answers.get(part).unwrap_or_default());
+                    format!("✖ {answer}, expected {expected}{duration}")
+                }
+                Verdict::Unverified => {
+                    summary.unverified += 1;
+                    format!("? {answer}, no known answer{duration}")
+                }
+                Verdict::Panicked(message) => {
+                    summary.panicked += 1;
+                    format!("✖ {message}")
+                }
+            };
+
+            println!("  Part {part}: {line}");
+        }
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{collect_inputs, get_verdict, Verdict};
+    use crate::template::run_multi::child_commands::{PartOutput, SolutionOutput};
+
+    fn part(answer: &str) -> PartOutput {
+        PartOutput {
+            part: 1,
+            answer: Some(answer.into()),
+            error: None,
+            duration: None,
+        }
+    }
+
+    fn output() -> SolutionOutput {
+        SolutionOutput {
+            stdout: vec![],
+            stderr: vec![],
+            success: true,
+            timed_out: false,
+        }
+    }
+
+    #[test]
+    fn skips_empty_inputs() {
+        let dir = env::temp_dir().join(format!("aoc-verify-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("alice.txt"), "1 2\n").unwrap();
+        fs::write(dir.join("alice.answers"), "1: 3\n").unwrap();
+        fs::write(dir.join("bob.txt"), "").unwrap();
+
+        let inputs = collect_inputs(&dir);
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(inputs.unwrap(), vec![dir.join("alice.txt")]);
+    }
+
+    #[test]
+    fn compares_letter_art_by_its_letters() {
+        let art = [
+            "#..#.###..####.",
+            "#..#..#...#....",
+            "####..#...###..",
+            "#..#..#...#....",
+            "#..#..#...#....",
+            "#..#.###..####.",
+        ]
+        .join("\n");
+
+        let verdict = get_verdict(Some(&part(&art)), Some("HIE"), &output());
+        assert!(matches!(verdict, Some(Verdict::Pass)));
+
+        let verdict = get_verdict(Some(&part(&art)), Some("HIF"), &output());
+        assert!(matches!(verdict, Some(Verdict::Fail)));
+    }
+
+    #[test]
+    fn compares_plain_answers() {
+        let verdict = get_verdict(Some(&part("42")), Some("42"), &output());
+        assert!(matches!(verdict, Some(Verdict::Pass)));
+
+        let verdict = get_verdict(Some(&part("42")), None, &output());
+        assert!(matches!(verdict, Some(Verdict::Unverified)));
+    }
+}
@@ -0,0 +1,231 @@
+use crate::template::timings::{format_nanos, PartTiming, Timings};
+use crate::template::{output, Day};
+
+/// Differences larger than this many standard errors are considered significant (~95% confidence).
+const SIGNIFICANCE_THRESHOLD: f64 = 1.96;
+
+/// A part benched again, compared to its stored timing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub part: u8,
+    pub stored: PartTiming,
+    pub current: PartTiming,
+}
+
+impl Comparison {
+    /// Compares all parts that have both a stored and a current timing.
+    pub fn of(stored: &Timings, current: &Timings) -> Vec<Self> {
+        current
+            .data
+            .iter()
+            .filter_map(|timing| {
+                let stored = stored.data.iter().find(|t| t.day == timing.day)?;
+                Some((timing, stored))
+            })
+            .flat_map(|(timing, stored)| {
+                [
+                    (1, stored.part_1, timing.part_1),
+                    (2, stored.part_2, timing.part_2),
+                ]
+                .into_iter()
+                .filter_map(|(part, stored, current)| {
+                    Some(Comparison {
+                        day: timing.day,
+                        part,
+                        stored: stored?,
+                        current: current?,
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// The change of the mean duration in nanoseconds, positive if the part got slower.
+    pub fn delta(&self) -> f64 {
+        self.current.nanos - self.stored.nanos
+    }
+
+    /// The change of the mean duration relative to the stored timing, in percent.
+    pub fn percent(&self) -> f64 {
+        if self.stored.nanos == 0.0 {
+            0.0
+        } else {
+            self.delta() / self.stored.nanos * 100.0
+        }
+    }
+
+    /// Whether the difference of the means is unlikely to be noise, based on the standard error of
+    /// both timings (Welch's t-test). `None` if the sample statistics of a timing are unknown.
+    pub fn is_significant(&self) -> Option<bool> {
+        let variance = |timing: &PartTiming| -> Option<f64> {
+            #[allow(clippy::cast_precision_loss)]
+            let samples = timing.samples.filter(|x| *x > 0)? as f64;
+            Some(timing.std_dev?.powi(2) / samples)
+        };
+
+        let standard_error = (variance(&self.stored)? + variance(&self.current)?).sqrt();
+
+        if standard_error == 0.0 {
+            return Some(self.delta() != 0.0);
+        }
+
+        Some(self.delta().abs() / standard_error > SIGNIFICANCE_THRESHOLD)
+    }
+
+    /// Whether the part got slower by more than `threshold` percent. Differences of unknown
+    /// significance count as regressions, as they cannot be ruled out.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent() > threshold && self.is_significant() != Some(false)
+    }
+}
+
+fn format_delta(nanos: f64) -> String {
+    let sign = if nanos < 0.0 { '-' } else { '+' };
+    format!("{sign}{}", format_nanos(nanos.abs()))
+}
+
+fn format_row(comparison: &Comparison, threshold: Option<f64>) -> Vec<String> {
+    let significance = match comparison.is_significant() {
+        Some(true) if comparison.delta() > 0.0 => "slower",
+        Some(true) => "faster",
+        Some(false) => "noise",
+        None => "?",
+    };
+
+    let mut row = vec![
+        comparison.day.to_string(),
+        comparison.part.to_string(),
+        comparison.stored.to_string(),
+        comparison.current.to_string(),
+        format_delta(comparison.delta()),
+        format!("{:+.1}%", comparison.percent()),
+        significance.to_string(),
+    ];
+
+    if let Some(threshold) = threshold {
+        let marker = if comparison.is_regression(threshold) {
+            "⚠ regression"
+        } else {
+            ""
+        };
+        row.push(marker.into());
+    }
+
+    row
+}
+
+/// Prints the comparisons as a table, marking regressions beyond `threshold` percent.
+pub fn print(comparisons: &[Comparison], threshold: Option<f64>) {
+    println!("\n{}", output::reporter().strong("Comparison"));
+
+    if comparisons.is_empty() {
+        println!("No stored timings to compare against. Run `cargo time --store` to store some.");
+        return;
+    }
+
+    let mut header: Vec<String> = [
+        "Day",
+        "Part",
+        "Stored",
+        "Now",
+        "Delta",
+        "Change",
+        "Significance",
+    ]
+    .map(String::from)
+    .to_vec();
+
+    // regressions are marked in an extra column.
+    if threshold.is_some() {
+        header.push(String::new());
+    }
+
+    let rows: Vec<Vec<String>> = comparisons
+        .iter()
+        .map(|comparison| format_row(comparison, threshold))
+        .collect();
+
+    output::print_table(&header, &rows);
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_delta, Comparison};
+    use crate::{
+        day,
+        template::timings::{PartTiming, Timing, Timings},
+    };
+
+    fn part(millis: f64, std_dev: Option<f64>) -> PartTiming {
+        PartTiming {
+            nanos: millis * 1_000_000.0,
+            samples: std_dev.map(|_| 100),
+            std_dev: std_dev.map(|x| x * 1_000_000.0),
+        }
+    }
+
+    fn comparison(stored: PartTiming, current: PartTiming) -> Comparison {
+        Comparison {
+            day: day!(1),
+            part: 1,
+            stored,
+            current,
+        }
+    }
+
+    #[test]
+    fn compares_parts_with_stored_timings() {
+        let timing = |day, part_1, part_2| Timing {
+            day,
+            part_1,
+            part_2,
+            total_nanos: 0.0,
+            status: Default::default(),
+        };
+        let stored = Timings {
+            data: vec![
+                timing(day!(1), Some(part(10.0, None)), None),
+                timing(day!(2), Some(part(20.0, None)), None),
+            ],
+        };
+        let current = Timings {
+            data: vec![
+                timing(day!(1), Some(part(12.0, None)), Some(part(5.0, None))),
+                timing(day!(3), Some(part(30.0, None)), None),
+            ],
+        };
+
+        let comparisons = Comparison::of(&stored, &current);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].day, day!(1));
+        assert_eq!(comparisons[0].part, 1);
+        assert!((comparisons[0].percent() - 20.0).abs() < 1e-9);
+        assert_eq!(format_delta(comparisons[0].delta()), "+2.0ms");
+    }
+
+    #[test]
+    fn detects_significant_changes() {
+        // standard error of 0.1ms for 100 samples each.
+        let noisy = comparison(part(10.0, Some(0.7)), part(10.1, Some(0.7)));
+        assert_eq!(noisy.is_significant(), Some(false));
+        assert!(!noisy.is_regression(0.5));
+
+        let slower = comparison(part(10.0, Some(0.7)), part(11.0, Some(0.7)));
+        assert_eq!(slower.is_significant(), Some(true));
+        assert!(slower.is_regression(5.0));
+        assert!(!slower.is_regression(20.0));
+
+        let faster = comparison(part(11.0, Some(0.7)), part(10.0, Some(0.7)));
+        assert!(!faster.is_regression(0.0));
+    }
+
+    #[test]
+    fn treats_unknown_significance_as_regressions() {
+        let unknown = comparison(part(10.0, None), part(11.0, Some(0.7)));
+        assert_eq!(unknown.is_significant(), None);
+        assert!(unknown.is_regression(5.0));
+    }
+}
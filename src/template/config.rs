@@ -0,0 +1,668 @@
+//! Project configuration, read from `aoc.toml` in the project root.
+//!
+//! The project root is the directory containing `Cargo.toml`, taken from `CARGO_MANIFEST_DIR`, which cargo sets
+//! when running binaries and tests, or else from the directory the crate was compiled in. Relative paths are
+//! resolved against it, so commands and tests work regardless of the current directory.
+//!
+//! ```toml
+//! year = 2024
+//! data_dir = "data"
+//! template = "src/template.txt"
+//! readme = "README.md"
+//!
+//! [bench]
+//! time_ms = 1000
+//! min_samples = 10
+//! max_samples = 10000
+//!
+//! [input]
+//! normalize = "crlf"
+//!
+//! [input.days]
+//! 02 = "strict"
+//!
+//! [budget]
+//! total = "1s"
+//! show_in_readme = true
+//!
+//! [budget.days]
+//! 01 = "50ms"
+//!
+//! [budget.days.02]
+//! part_2 = "500µs"
+//! ```
+//!
+//! All keys are optional and can be overridden with environment variables named after them,
+//! e.g. `AOC_YEAR`, `AOC_DATA_DIR` or `AOC_BENCH_MIN_SAMPLES`.
+//!
+//! The file is read with a small parser instead of a TOML crate, as the config needs only a few kinds of values
+//! and every solution binary links this library. It accepts this subset of TOML and rejects anything else with
+//! the line it appears on:
+//!
+//! - `[table]` headers and `key = value` pairs, where table names and keys are bare keys (`A-Za-z0-9_-`),
+//!   optionally joined with dots like `budget.days.02`,
+//! - strings in double quotes with the escapes `\\`, `\"`, `\n` and `\t`, integers with optional `_` separators,
+//!   and `true` or `false`,
+//! - `#` comments and blank lines.
+//!
+//! Literal and multi-line strings, floats, dates, arrays, inline tables and quoted keys are not supported.
+
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    time::Duration,
+};
+
+use crate::template::timings::parse_nanos;
+use crate::template::{Day, Normalize};
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// The year passed to aoc-cli, which defaults to the current year if not set.
+    pub year: Option<u16>,
+    /// Contains inputs, examples, puzzle descriptions and timings.
+    pub data_dir: PathBuf,
+    /// The module template used by `cargo scaffold`. If not set, the built-in `src/template.txt` is used.
+    pub template: Option<PathBuf>,
+    /// The readme the benchmark table is written to.
+    pub readme: PathBuf,
+    pub bench: BenchConfig,
+    pub input: InputConfig,
+    pub budget: BudgetConfig,
+}
+
+/// How long parts are benched by `--time`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Approximate time spent benching each part.
+    pub time: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+/// How inputs are normalised before solutions see them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputConfig {
+    /// The default of all days.
+    pub normalize: Normalize,
+    /// Overrides for single days.
+    pub days: BTreeMap<Day, Normalize>,
+}
+
+/// How long solutions may take, checked by `cargo time`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BudgetConfig {
+    /// The budget of all days together.
+    pub total: Option<Duration>,
+    pub days: BTreeMap<Day, DayBudget>,
+    /// Whether the readme benchmark table shows how much of the budgets is used.
+    pub show_in_readme: bool,
+}
+
+/// The budget of a single day and its parts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DayBudget {
+    pub total: Option<Duration>,
+    pub parts: [Option<Duration>; 2],
+}
+
+impl BudgetConfig {
+    pub fn is_empty(&self) -> bool {
+        self.total.is_none() && self.days.is_empty()
+    }
+
+    fn set(&mut self, key: &str, value: &Value) -> Result<(), String> {
+        match key {
+            "total" => self.total = Some(value.to_duration()?),
+            "show_in_readme" => self.show_in_readme = value.to_bool()?,
+            _ => {
+                // `days.01` is the budget of the whole day, `days.01.part_1` of one of its parts.
+                let key = key.strip_prefix("days.").ok_or("unknown key")?;
+                let (day, field) = key.split_once('.').unwrap_or((key, "total"));
+                let day: Day = day.parse().map_err(|_| "not a day")?;
+                let budget = self.days.entry(day).or_default();
+
+                match field {
+                    "total" => budget.total = Some(value.to_duration()?),
+                    "part_1" => budget.parts[0] = Some(value.to_duration()?),
+                    "part_2" => budget.parts[1] = Some(value.to_duration()?),
+                    _ => return Err("unknown key".into()),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: None,
+            data_dir: "data".into(),
+            template: None,
+            readme: "README.md".into(),
+            bench: BenchConfig {
+                time: Duration::from_secs(1),
+                min_samples: 10,
+                max_samples: 10000,
+            },
+            input: InputConfig {
+                normalize: Normalize::RAW,
+                days: BTreeMap::new(),
+            },
+            budget: BudgetConfig::default(),
+        }
+    }
+}
+
+/// Keys that can be set in the config file, with the environment variables overriding them.
+const KEYS: [(&str, &str); 10] = [
+    ("year", "AOC_YEAR"),
+    ("data_dir", "AOC_DATA_DIR"),
+    ("template", "AOC_TEMPLATE"),
+    ("readme", "AOC_README"),
+    ("bench.time_ms", "AOC_BENCH_TIME_MS"),
+    ("bench.min_samples", "AOC_BENCH_MIN_SAMPLES"),
+    ("bench.max_samples", "AOC_BENCH_MAX_SAMPLES"),
+    ("input.normalize", "AOC_INPUT_NORMALIZE"),
+    ("budget.total", "AOC_BUDGET_TOTAL"),
+    ("budget.show_in_readme", "AOC_BUDGET_SHOW_IN_README"),
+];
+
+impl Config {
+    /// Reads the config file if present and applies the environment overrides.
+    pub fn load() -> Result<Self, ConfigError> {
+        let contents = match fs::read_to_string(root().join(CONFIG_FILE)) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(ConfigError(format!("could not read {CONFIG_FILE}: {e}"))),
+        };
+
+        let mut config = Self::parse(&contents)?;
+
+        for (key, var) in KEYS {
+            if let Some(value) = env::var(var).ok().filter(|x| !x.is_empty()) {
+                config
+                    .set(key, Value::Env(value))
+                    .map_err(|e| ConfigError(format!("{var}: {e}")))?;
+            }
+        }
+
+        config.validate()?;
+        Ok(config.anchor(root()))
+    }
+
+    /// Parses the contents of a config file, supporting the subset of TOML described in the
+    /// [module documentation](self).
+    pub fn parse(s: &str) -> Result<Self, ConfigError> {
+        let mut config = Config::default();
+        let mut table = String::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let error =
+                |message: String| ConfigError(format!("{CONFIG_FILE}:{}: {message}", i + 1));
+            let line = strip_comment(line).trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|x| x.strip_suffix(']')) {
+                let name = name.trim();
+                if !is_bare_key(name) {
+                    return Err(error(format!(
+                        "expecting a table name like `[budget.days]`, found `{line}`"
+                    )));
+                }
+                table = format!("{name}.");
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(error(format!("expecting `key = value`, found `{line}`")));
+            };
+
+            let key = key.trim();
+            if !is_bare_key(key) {
+                return Err(error(format!(
+                    "expecting a key like `min_samples`, found `{key}`"
+                )));
+            }
+
+            let key = format!("{table}{key}");
+            let value = Value::parse(value.trim()).map_err(error)?;
+            config
+                .set(&key, value)
+                .map_err(|e| error(format!("`{key}`: {e}")))?;
+        }
+
+        Ok(config)
+    }
+
+    fn set(&mut self, key: &str, value: Value) -> Result<(), String> {
+        match key {
+            "year" => self.year = Some(value.to_int()?),
+            "data_dir" => self.data_dir = value.to_str()?.into(),
+            "template" => self.template = Some(value.to_str()?.into()),
+            "readme" => self.readme = value.to_str()?.into(),
+            "bench.time_ms" => self.bench.time = Duration::from_millis(value.to_int()?),
+            "bench.min_samples" => self.bench.min_samples = value.to_int()?,
+            "bench.max_samples" => self.bench.max_samples = value.to_int()?,
+            "input.normalize" => self.input.normalize = value.to_str()?.parse()?,
+            _ => {
+                if let Some(key) = key.strip_prefix("budget.") {
+                    return self.budget.set(key, &value);
+                }
+
+                let day = key
+                    .strip_prefix("input.days.")
+                    .ok_or("unknown key")?
+                    .parse()
+                    .map_err(|_| "not a day")?;
+                self.input.days.insert(day, value.to_str()?.parse()?);
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.bench.min_samples == 0 || self.bench.min_samples > self.bench.max_samples {
+            return Err(ConfigError(
+                "`bench.min_samples` has to be at least 1 and at most `bench.max_samples`".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Resolves relative paths against a root directory.
+    fn anchor(self, root: &Path) -> Self {
+        Config {
+            data_dir: root.join(self.data_dir),
+            template: self.template.map(|x| root.join(x)),
+            readme: root.join(self.readme),
+            ..self
+        }
+    }
+
+    /// Returns the path of a file in the data directory, e.g. `data/examples/01.txt`.
+    pub fn data_path(&self, folder: &str, file: &str) -> PathBuf {
+        self.data_dir.join(folder).join(file)
+    }
+
+    pub fn timings_path(&self) -> PathBuf {
+        self.data_dir.join("timings.json")
+    }
+
+    /// Returns how the inputs of a day are normalised.
+    pub fn normalize(&self, day: Day) -> Normalize {
+        self.input
+            .days
+            .get(&day)
+            .copied()
+            .unwrap_or(self.input.normalize)
+    }
+}
+
+/// Returns the path of a day's solution. Solutions live where cargo discovers binaries, so this is not configurable.
+pub fn bin_path(day: Day) -> PathBuf {
+    root().join("src/bin").join(format!("{day}.rs"))
+}
+
+/// Returns the project root, see the [module documentation](self).
+pub fn root() -> &'static Path {
+    static ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+    ROOT.get_or_init(|| {
+        env::var_os("CARGO_MANIFEST_DIR")
+            .filter(|x| !x.is_empty())
+            .map_or_else(|| env!("CARGO_MANIFEST_DIR").into(), PathBuf::from)
+    })
+}
+
+/// Shortens a path within the project root for messages, e.g. `data/inputs/01.txt`.
+pub fn relative(path: &Path) -> &Path {
+    path.strip_prefix(root()).unwrap_or(path)
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Loads the configuration of this project if it was not loaded yet. The CLI and the solution binaries call this
+/// before anything else, so an invalid configuration is reported as a usage error.
+pub fn init() -> Result<&'static Config, ConfigError> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+
+    let config = Config::load()?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// Returns the configuration of this project, loading it once.
+///
+/// # Panics
+///
+/// If the configuration is invalid and was not loaded with [`init`] before.
+pub fn get() -> &'static Config {
+    init().unwrap_or_else(|e| panic!("invalid configuration: {e}"))
+}
+
+/// Whether a table name or key consists of bare keys joined with dots, e.g. `budget.days.02`.
+fn is_bare_key(s: &str) -> bool {
+    s.split('.').all(|part| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    })
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            '\\' if in_string => {
+                escaped = !escaped;
+                continue;
+            }
+            '"' if !escaped => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+        escaped = false;
+    }
+
+    line
+}
+
+enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    /// A value of an environment variable, which is interpreted according to the key it is set for.
+    Env(String),
+}
+
+impl Value {
+    fn parse(s: &str) -> Result<Self, String> {
+        if let Some(inner) = s.strip_prefix('"').and_then(|x| x.strip_suffix('"')) {
+            return Ok(Value::String(unescape(inner)?));
+        }
+
+        match s {
+            "true" => return Ok(Value::Boolean(true)),
+            "false" => return Ok(Value::Boolean(false)),
+            _ => {}
+        }
+
+        s.replace('_', "")
+            .parse()
+            .map(Value::Integer)
+            .map_err(|_| format!("expecting a string, an integer or a boolean, found `{s}`"))
+    }
+
+    fn to_str(&self) -> Result<&str, String> {
+        match self {
+            Value::String(s) | Value::Env(s) => Ok(s),
+            Value::Integer(x) => Err(format!("expecting a string, found `{x}`")),
+            Value::Boolean(x) => Err(format!("expecting a string, found `{x}`")),
+        }
+    }
+
+    fn to_bool(&self) -> Result<bool, String> {
+        match self {
+            Value::Boolean(x) => Ok(*x),
+            Value::Env(s) if s == "true" || s == "1" => Ok(true),
+            Value::Env(s) if s == "false" || s == "0" => Ok(false),
+            Value::Env(s) | Value::String(s) => Err(format!("expecting a boolean, found \"{s}\"")),
+            Value::Integer(x) => Err(format!("expecting a boolean, found `{x}`")),
+        }
+    }
+
+    /// Parses a duration with a unit like `500µs`, `50ms` or `1s`.
+    fn to_duration(&self) -> Result<Duration, String> {
+        let s = self.to_str()?.trim();
+        let error = || format!("expecting a duration like \"50ms\", found \"{s}\"");
+
+        if !s.ends_with('s') {
+            return Err(error());
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        parse_nanos(&s.replace("us", "µs"))
+            .filter(|x| x.is_finite() && *x >= 0.0)
+            .map(|x| Duration::from_nanos(x.round() as u64))
+            .ok_or_else(error)
+    }
+
+    fn to_int<T: TryFrom<i64>>(&self) -> Result<T, String> {
+        let value = match self {
+            Value::Integer(x) => *x,
+            Value::Env(s) => s
+                .parse()
+                .map_err(|_| format!("expecting an integer, found `{s}`"))?,
+            Value::String(s) => return Err(format!("expecting an integer, found \"{s}\"")),
+            Value::Boolean(x) => return Err(format!("expecting an integer, found `{x}`")),
+        };
+
+        T::try_from(value).map_err(|_| format!("`{value}` is out of range"))
+    }
+}
+
+fn unescape(s: &str) -> Result<String, String> {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '"' {
+            return Err("unescaped `\"` in string".into());
+        }
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('\\') => result.push('\\'),
+            Some('"') => result.push('"'),
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            x => {
+                return Err(format!(
+                    "unsupported escape sequence `\\{}`",
+                    x.unwrap_or(' ')
+                ))
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// An error in the config file or its environment overrides.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConfigError(String);
+
+impl std::error::Error for ConfigError {}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        collections::BTreeMap,
+        path::{Path, PathBuf},
+        time::Duration,
+    };
+
+    use super::{BenchConfig, BudgetConfig, Config, DayBudget, InputConfig, Value};
+    use crate::{day, template::Normalize};
+
+    #[test]
+    fn parses_defaults() {
+        assert_eq!(Config::parse(""), Ok(Config::default()));
+    }
+
+    #[test]
+    fn parses_all_keys() {
+        let config = Config::parse(
+            r#"
+            # comments and blank lines are ignored.
+            year = 2_023
+            data_dir = "../inputs" # trailing comments, too.
+            template = "templates/#1.txt"
+            readme = "docs/README.md"
+
+            [bench]
+            time_ms = 500
+            min_samples = 5
+            max_samples = 100
+
+            [input]
+            normalize = "strict"
+
+            [input.days]
+            02 = "raw"
+
+            [budget]
+            total = "1s"
+            show_in_readme = true
+
+            [budget.days]
+            01 = "50ms"
+
+            [budget.days.02]
+            part_2 = "500us"
+            "#,
+        );
+
+        assert_eq!(
+            config,
+            Ok(Config {
+                year: Some(2023),
+                data_dir: "../inputs".into(),
+                template: Some("templates/#1.txt".into()),
+                readme: "docs/README.md".into(),
+                bench: BenchConfig {
+                    time: Duration::from_millis(500),
+                    min_samples: 5,
+                    max_samples: 100,
+                },
+                input: InputConfig {
+                    normalize: Normalize::STRICT,
+                    days: BTreeMap::from([(day!(2), Normalize::RAW)]),
+                },
+                budget: BudgetConfig {
+                    total: Some(Duration::from_secs(1)),
+                    days: BTreeMap::from([
+                        (
+                            day!(1),
+                            DayBudget {
+                                total: Some(Duration::from_millis(50)),
+                                parts: [None, None],
+                            }
+                        ),
+                        (
+                            day!(2),
+                            DayBudget {
+                                total: None,
+                                parts: [None, Some(Duration::from_micros(500))],
+                            }
+                        ),
+                    ]),
+                    show_in_readme: true,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn selects_normalization_of_day() {
+        let config = Config::parse("[input.days]\n2 = \"crlf, trim\"").unwrap();
+        assert_eq!(config.normalize(day!(1)), Normalize::RAW);
+        assert!(config.normalize(day!(2)).trim);
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        for s in [
+            "foo = 1",
+            "year = \"2023\"",
+            "year = 70000",
+            "data_dir = 1",
+            "data_dir",
+            "[bench]\nyear = 2023",
+            "readme = \"a\\qb\"",
+            "[input]\nnormalize = \"lf\"",
+            "[input.days]\n26 = \"raw\"",
+            "[budget]\ntotal = \"1000\"",
+            "[budget]\ntotal = 1000",
+            "[budget]\nshow_in_readme = \"yes\"",
+            "[budget.days.01]\npart_3 = \"1ms\"",
+        ] {
+            assert!(Config::parse(s).is_err(), "{s}");
+        }
+
+        let error = Config::parse("year = 2023\nfoo = 1").unwrap_err();
+        assert_eq!(error.to_string(), "aoc.toml:2: `foo`: unknown key");
+    }
+
+    #[test]
+    fn rejects_unsupported_toml() {
+        for s in [
+            "data_dir = 'data'",
+            "data_dir = \"\"\"data\"\"\"",
+            "[bench]\ntime_ms = 1.5",
+            "year = [2023]",
+            "bench = { time_ms = 500 }",
+            "[[bench]]",
+            "[input.days]\n\"02\" = \"raw\"",
+            "[budget. days]",
+        ] {
+            assert!(Config::parse(s).is_err(), "{s}");
+        }
+
+        let error = Config::parse("[input.days]\n\"02\" = \"raw\"").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "aoc.toml:2: expecting a key like `min_samples`, found `\"02\"`"
+        );
+
+        // dotted keys are supported like in TOML.
+        let config = Config::parse("bench.min_samples = 5").unwrap();
+        assert_eq!(config.bench.min_samples, 5);
+    }
+
+    #[test]
+    fn anchors_relative_paths() {
+        let config = Config::parse("data_dir = \"/srv/aoc\"\ntemplate = \"t.txt\"")
+            .unwrap()
+            .anchor(Path::new("/repo"));
+        assert_eq!(config.data_dir, PathBuf::from("/srv/aoc"));
+        assert_eq!(config.template, Some(PathBuf::from("/repo/t.txt")));
+        assert_eq!(config.readme, PathBuf::from("/repo/README.md"));
+    }
+
+    #[test]
+    fn applies_overrides() {
+        let mut config = Config::default();
+        config.set("year", Value::Env("2015".into())).unwrap();
+        config.set("data_dir", Value::Env("2015".into())).unwrap();
+        assert_eq!(config.year, Some(2015));
+        assert_eq!(config.data_dir, PathBuf::from("2015"));
+        assert!(config.set("year", Value::Env("next".into())).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::str::FromStr;
+
+use crate::template::{all_days, config, profile, submissions, Day};
+
+/// A selection of days, as accepted by the commands of the CLI.
+///
+/// Days can be selected by number (`3`), by range (`1..5` excludes the end, `1..=5` includes it),
+/// as a comma-separated list of numbers and ranges (`1,4,7..=9`) or with one of the keywords
+/// `all`, `today`, `latest` (the last scaffolded day) and `unsolved` (scaffolded days with an unsolved part).
+///
+/// ```
+/// # use advent_of_code::template::DaySet;
+/// let days: DaySet = "1,4..=6".parse().unwrap();
+/// assert_eq!(days.resolve().unwrap().len(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaySet {
+    Days(BTreeSet<Day>),
+    All,
+    Today,
+    Latest,
+    Unsolved,
+}
+
+impl DaySet {
+    /// Returns the selected days in ascending order.
+    /// Keywords are resolved against the current date and the scaffolded solutions.
+    pub fn resolve(&self) -> Result<Vec<Day>, DaySetResolveError> {
+        match self {
+            DaySet::Days(days) => Ok(days.iter().copied().collect()),
+            DaySet::All => Ok(all_days().collect()),
+            DaySet::Today => today().map(|day| vec![day]),
+            DaySet::Latest => all_days()
+                .filter(|day| is_scaffolded(*day))
+                .last()
+                .map(|day| vec![day])
+                .ok_or(DaySetResolveError::NothingScaffolded),
+            DaySet::Unsolved => Ok(all_days()
+                .filter(|day| is_scaffolded(*day) && !is_solved(*day))
+                .collect()),
+        }
+    }
+}
+
+impl From<Day> for DaySet {
+    fn from(day: Day) -> Self {
+        DaySet::Days(BTreeSet::from([day]))
+    }
+}
+
+fn is_scaffolded(day: Day) -> bool {
+    config::bin_path(day).exists()
+}
+
+fn is_solved(day: Day) -> bool {
+    let puzzle = fs::read_to_string(profile::get_puzzle_path(day)).unwrap_or_default();
+    submissions::next_unsolved_part(&puzzle, &submissions::read_log(day)).is_none()
+}
+
+#[cfg(feature = "today")]
+fn today() -> Result<Day, DaySetResolveError> {
+    Day::today().ok_or(DaySetResolveError::NotAdvent)
+}
+
+#[cfg(not(feature = "today"))]
+fn today() -> Result<Day, DaySetResolveError> {
+    Err(DaySetResolveError::TodayDisabled)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => return Ok(DaySet::All),
+            "today" => return Ok(DaySet::Today),
+            "latest" => return Ok(DaySet::Latest),
+            "unsolved" => return Ok(DaySet::Unsolved),
+            _ => {}
+        }
+
+        let mut days = BTreeSet::new();
+
+        for item in s.split(',') {
+            let item = item.trim();
+
+            if let Some((start, end)) = item.split_once("..=") {
+                days.extend(parse_range(item, start, end, 0)?);
+            } else if let Some((start, end)) = item.split_once("..") {
+                days.extend(parse_range(item, start, end, 1)?);
+            } else {
+                days.insert(parse_day(item)?);
+            }
+        }
+
+        Ok(DaySet::Days(days))
+    }
+}
+
+fn parse_day(s: &str) -> Result<Day, DaySetFromStrError> {
+    s.parse()
+        .map_err(|_| DaySetFromStrError(format!("`{s}` is not a day between 1 and 25")))
+}
+
+/// Parses the bounds of a range, `exclude_end` being 1 for exclusive and 0 for inclusive ranges.
+fn parse_range(
+    item: &str,
+    start: &str,
+    end: &str,
+    exclude_end: u8,
+) -> Result<impl Iterator<Item = Day>, DaySetFromStrError> {
+    let start = parse_day(start)?.into_inner();
+    let end = parse_day(end)?.into_inner();
+
+    if start + exclude_end > end {
+        return Err(DaySetFromStrError(format!("range `{item}` is empty")));
+    }
+
+    Ok((start..=end - exclude_end).filter_map(Day::new))
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}, expecting a day, a range like `1..=5`, a list like `1,4,7` \
+            or one of `all`, `today`, `latest` and `unsolved`",
+            self.0
+        )
+    }
+}
+
+/// An error which can be returned when resolving the keywords of a [`DaySet`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum DaySetResolveError {
+    NotAdvent,
+    TodayDisabled,
+    NothingScaffolded,
+}
+
+impl Error for DaySetResolveError {}
+
+impl Display for DaySetResolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DaySetResolveError::NotAdvent => {
+                write!(
+                    f,
+                    "`today` can only be used between the 1st and the 25th of december."
+                )
+            }
+            DaySetResolveError::TodayDisabled => {
+                write!(f, "`today` requires the `today` feature to be enabled.")
+            }
+            DaySetResolveError::NothingScaffolded => {
+                write!(f, "`latest` requires at least one scaffolded day.")
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DaySet;
+    use crate::template::Day;
+
+    fn days(s: &str) -> Vec<u8> {
+        s.parse::<DaySet>()
+            .unwrap()
+            .resolve()
+            .unwrap()
+            .into_iter()
+            .map(Day::into_inner)
+            .collect()
+    }
+
+    #[test]
+    fn parses_single_days() {
+        assert_eq!(days("3"), vec![3]);
+        assert_eq!(days("03"), vec![3]);
+    }
+
+    #[test]
+    fn parses_ranges() {
+        assert_eq!(days("1..5"), vec![1, 2, 3, 4]);
+        assert_eq!(days("1..=5"), vec![1, 2, 3, 4, 5]);
+        assert_eq!(days("25..=25"), vec![25]);
+    }
+
+    #[test]
+    fn parses_lists() {
+        assert_eq!(days("7,1,4"), vec![1, 4, 7]);
+        assert_eq!(days("1..=3, 2, 10"), vec![1, 2, 3, 10]);
+    }
+
+    #[test]
+    fn parses_keywords() {
+        assert_eq!("all".parse(), Ok(DaySet::All));
+        assert_eq!("today".parse(), Ok(DaySet::Today));
+        assert_eq!("latest".parse(), Ok(DaySet::Latest));
+        assert_eq!("unsolved".parse(), Ok(DaySet::Unsolved));
+        assert_eq!(days("all").len(), 25);
+    }
+
+    #[test]
+    fn rejects_invalid_sets() {
+        for s in [
+            "", "0", "26", "1..1", "5..=3", "1..26", "1,,2", "foo", "1-5",
+        ] {
+            assert!(s.parse::<DaySet>().is_err(), "{s}");
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::{fmt::Display, io, path::PathBuf};
+
+use crate::template::aoc_cli::AocCommandError;
+use crate::template::config::ConfigError;
+
+/// An error returned by the commands of the CLI.
+///
+/// Every variant maps to an exit code of the `advent_of_code` binary, see [`Error::exit_code`].
+#[derive(Debug)]
+pub enum Error {
+    /// Invalid command-line arguments or configuration.
+    Usage(String),
+    /// aoc-cli is not installed or failed.
+    AocCli(AocCommandError),
+    /// Reading or writing a file, or starting a process failed.
+    Io {
+        /// What was being done, e.g. `create module file "src/bin/01.rs"`.
+        action: String,
+        source: io::Error,
+    },
+    /// A file that has to be created first, e.g. an input that has not been downloaded yet.
+    MissingFile {
+        path: PathBuf,
+        /// How to create the file.
+        hint: String,
+    },
+    /// A solution failed, or did not produce the expected answers.
+    Failed(String),
+}
+
+impl Error {
+    pub fn io(action: impl Into<String>, source: io::Error) -> Self {
+        Self::Io {
+            action: action.into(),
+            source,
+        }
+    }
+
+    /// Returns the exit code documented in the readme.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Failed(_) => 1,
+            Error::Usage(_) => 2,
+            Error::AocCli(_) => 3,
+            Error::Io { .. } | Error::MissingFile { .. } => 4,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(message) | Error::Failed(message) => f.write_str(message),
+            Error::AocCli(AocCommandError::CommandNotFound) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            Error::AocCli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            Error::Io { action, source } => write!(f, "failed to {action}: {source}"),
+            Error::MissingFile { path, hint } => {
+                write!(f, "\"{}\" does not exist. {hint}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<AocCommandError> for Error {
+    fn from(e: AocCommandError) -> Self {
+        Error::AocCli(e)
+    }
+}
+
+impl From<ConfigError> for Error {
+    fn from(e: ConfigError) -> Self {
+        Error::Usage(e.to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{io, path::PathBuf};
+
+    use super::Error;
+    use crate::template::aoc_cli::AocCommandError;
+
+    #[test]
+    fn maps_errors_to_exit_codes() {
+        assert_eq!(Error::Failed("1 of 2 days failed.".into()).exit_code(), 1);
+        assert_eq!(Error::Usage("No command specified.".into()).exit_code(), 2);
+        assert_eq!(Error::from(AocCommandError::CommandNotFound).exit_code(), 3);
+        assert_eq!(
+            Error::io("create file", io::ErrorKind::NotFound.into()).exit_code(),
+            4
+        );
+    }
+
+    #[test]
+    fn displays_io_errors_with_action() {
+        let error = Error::io(
+            "create module file \"src/bin/01.rs\"",
+            io::Error::new(io::ErrorKind::AlreadyExists, "file exists"),
+        );
+        assert_eq!(
+            error.to_string(),
+            "failed to create module file \"src/bin/01.rs\": file exists"
+        );
+    }
+
+    #[test]
+    fn displays_missing_files_with_hint() {
+        let error = Error::MissingFile {
+            path: PathBuf::from("/aoc/data/inputs/01.txt"),
+            hint: "Run `cargo download 01` to download the input.".into(),
+        };
+        assert_eq!(error.exit_code(), 4);
+        assert_eq!(
+            error.to_string(),
+            "\"/aoc/data/inputs/01.txt\" does not exist. Run `cargo download 01` to download the input."
+        );
+    }
+}
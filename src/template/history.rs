@@ -0,0 +1,437 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::Error,
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::timings::{Timing, Timings};
+use crate::template::{config, Day};
+
+/// The version of the timings file written by this template.
+///
+/// - Version 1 stored the duration of each part as a formatted string like `"74.13ns"`.
+/// - Version 2 stores the mean, number of samples and standard deviation of each part as numbers.
+/// - Version 3 stores a history of runs, each with the timings of version 2 and the environment they were measured in.
+pub const VERSION: u32 = 3;
+
+/// Where and when a run was benchmarked. Fields are `None` if they could not be detected,
+/// and for runs migrated from files without a history.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Environment {
+    /// Seconds since the unix epoch.
+    pub timestamp: Option<u64>,
+    pub commit: Option<String>,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: Option<bool>,
+    pub rustc: Option<String>,
+    pub profile: Option<String>,
+    pub cpu: Option<String>,
+    pub hostname: Option<String>,
+}
+
+impl Environment {
+    /// Detects the environment of a run with the given cargo profile.
+    pub fn detect(profile: &str) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .map(|x| x.as_secs());
+
+        let hostname = fs::read_to_string("/proc/sys/kernel/hostname")
+            .ok()
+            .or_else(|| env::var("HOSTNAME").ok())
+            .or_else(|| env::var("COMPUTERNAME").ok())
+            .map(|x| x.trim().to_string())
+            .filter(|x| !x.is_empty());
+
+        Environment {
+            timestamp,
+            commit: command_output("git", &["rev-parse", "HEAD"]),
+            dirty: command_output("git", &["status", "--porcelain", "--untracked-files=no"])
+                .map(|x| !x.is_empty()),
+            rustc: command_output("rustc", &["--version"]),
+            profile: Some(profile.into()),
+            cpu: fs::read_to_string("/proc/cpuinfo")
+                .ok()
+                .and_then(|x| parse_cpu_model(&x)),
+            hostname,
+        }
+    }
+}
+
+/// Runs a command in the project root, returning its trimmed output if it succeeded.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(config::root())
+        .output()
+        .ok()
+        .filter(|x| x.status.success())?;
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Returns the first `model name` of a `/proc/cpuinfo` file.
+fn parse_cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| key.trim() == "model name")
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|x| !x.is_empty())
+}
+
+/// Formats seconds since the unix epoch as a UTC date, e.g. `2024-12-08 10:00 UTC`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let seconds = timestamp % 86_400;
+
+    // converts days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02} UTC",
+        seconds / 3_600,
+        seconds % 3_600 / 60
+    )
+}
+
+/// The timings of a single `cargo time --store` run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    pub environment: Environment,
+    pub timings: Timings,
+}
+
+/// All stored runs, oldest first.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Dehydrate the history to the timings file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(config::get().timings_path())?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the history from the timings file. If not present, returns an empty history.
+    pub fn read_from_file() -> Result<Self, String> {
+        let path = config::get().timings_path();
+        if !path.exists() {
+            return Ok(History::default());
+        }
+
+        fs::read_to_string(path)
+            .map_err(|x| x.to_string())
+            .and_then(History::try_from)
+    }
+
+    pub fn push(&mut self, environment: Environment, timings: Timings) {
+        self.entries.push(HistoryEntry {
+            environment,
+            timings,
+        });
+    }
+
+    /// Returns the most recent timing of every day.
+    pub fn latest(&self) -> Timings {
+        self.entries
+            .iter()
+            .fold(Timings::default(), |latest, entry| {
+                latest.merge(&entry.timings)
+            })
+    }
+
+    /// Returns all stored timings of a day, oldest first.
+    pub fn for_day(&self, day: Day) -> Vec<(&Environment, &Timing)> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let timing = entry.timings.data.iter().find(|t| t.day == day)?;
+                Some((&entry.environment, timing))
+            })
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&History> for JsonValue {
+    fn from(value: &History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("version".into(), JsonValue::Number(VERSION.into()));
+        map.insert(
+            "runs".into(),
+            JsonValue::Array(value.entries.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        // files written before the schema was versioned do not have a version.
+        let version = match json.get("version") {
+            None => 1,
+            Some(version) => version
+                .get::<f64>()
+                .filter(|x| x.fract() == 0.0)
+                .map(|x| *x as u32)
+                .ok_or("expected `json.version` to be an integer.")?,
+        };
+
+        if version == 0 || version > VERSION {
+            return Err(format!(
+                "unsupported version {version}, expected at most {VERSION}."
+            ));
+        }
+
+        // files before version 3 only stored the latest timings, without an environment.
+        if version < 3 {
+            let timings = Timings::from_json(json, version)?;
+            return Ok(History {
+                entries: vec![HistoryEntry {
+                    environment: Environment::default(),
+                    timings,
+                }],
+            });
+        }
+
+        let runs = json
+            .get("runs")
+            .ok_or("expected JSON document to have key `runs`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.runs` to be an array.")?;
+
+        Ok(History {
+            entries: runs
+                .iter()
+                .map(HistoryEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let environment = &value.environment;
+
+        let string = |x: &Option<String>| x.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        #[allow(clippy::cast_precision_loss)]
+        let timestamp = environment.timestamp.map(|x| x as f64);
+
+        map.insert(
+            "timestamp".into(),
+            timestamp.map_or(JsonValue::Null, JsonValue::Number),
+        );
+        map.insert("commit".into(), string(&environment.commit));
+        map.insert(
+            "dirty".into(),
+            environment
+                .dirty
+                .map_or(JsonValue::Null, JsonValue::Boolean),
+        );
+        map.insert("rustc".into(), string(&environment.rustc));
+        map.insert("profile".into(), string(&environment.profile));
+        map.insert("cpu".into(), string(&environment.cpu));
+        map.insert("hostname".into(), string(&environment.hostname));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run to be a JSON object.")?;
+
+        let string = |key: &str| -> Result<Option<String>, String> {
+            match json.get(key) {
+                None => Ok(None),
+                Some(v) if v.is_null() => Ok(None),
+                Some(v) => v
+                    .get::<String>()
+                    .cloned()
+                    .map(Some)
+                    .ok_or(format!("Expected run.{key} to be null or a string.")),
+            }
+        };
+
+        let timestamp = match json.get("timestamp") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Some(v) => Some(
+                v.get::<f64>()
+                    .map(|x| *x as u64)
+                    .ok_or("Expected run.timestamp to be null or a number.")?,
+            ),
+        };
+
+        let dirty = match json.get("dirty") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                *v.get::<bool>()
+                    .ok_or("Expected run.dirty to be null or a boolean.")?,
+            ),
+        };
+
+        Ok(HistoryEntry {
+            environment: Environment {
+                timestamp,
+                commit: string("commit")?,
+                dirty,
+                rustc: string("rustc")?,
+                profile: string("profile")?,
+                cpu: string("cpu")?,
+                hostname: string("hostname")?,
+            },
+            timings: Timings::from_json(json, 2)?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_timestamp, parse_cpu_model, Environment, History};
+    use crate::{
+        day,
+        template::timings::{PartTiming, Timing, Timings},
+    };
+
+    fn timing(day: u8, millis: f64) -> Timing {
+        let part = PartTiming {
+            nanos: millis * 1_000_000.0,
+            samples: Some(10),
+            std_dev: Some(millis * 1_000.0),
+        };
+
+        Timing {
+            day: crate::template::Day::new(day).unwrap(),
+            part_1: Some(part),
+            part_2: Some(part),
+            total_nanos: 2.0 * part.nanos,
+            status: Default::default(),
+        }
+    }
+
+    fn get_mock_history() -> History {
+        let mut history = History::default();
+        history.push(
+            Environment::default(),
+            Timings {
+                data: vec![timing(1, 10.0), timing(2, 20.0)],
+            },
+        );
+        history.push(
+            Environment {
+                timestamp: Some(1_733_652_000),
+                commit: Some("0123456789abcdef".into()),
+                dirty: Some(true),
+                rustc: Some("rustc 1.83.0 (90b35a623 2024-11-26)".into()),
+                profile: Some("release".into()),
+                cpu: Some("AMD Ryzen 7 5800X 8-Core Processor".into()),
+                hostname: Some("workstation".into()),
+            },
+            Timings {
+                data: vec![timing(2, 15.0), timing(3, 30.0)],
+            },
+        );
+        history
+    }
+
+    #[test]
+    fn derives_latest_timings() {
+        let latest = get_mock_history().latest();
+        assert_eq!(
+            latest.data,
+            vec![timing(1, 10.0), timing(2, 15.0), timing(3, 30.0)]
+        );
+    }
+
+    #[test]
+    fn lists_timings_of_a_day() {
+        let history = get_mock_history();
+        let runs = history.for_day(day!(2));
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].1, &timing(2, 20.0));
+        assert_eq!(runs[1].0.commit.as_deref(), Some("0123456789abcdef"));
+        assert!(history.for_day(day!(4)).is_empty());
+    }
+
+    #[test]
+    fn round_trips_history() {
+        let history = get_mock_history();
+        let json = JsonValue::from(&history).stringify().unwrap();
+        assert_eq!(History::try_from(json), Ok(history));
+    }
+
+    #[test]
+    fn migrates_version_2() {
+        let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1000000, "samples": 10, "std_dev": 2000 }, "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
+        let history = History::try_from(json).unwrap();
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!(history.entries[0].environment, Environment::default());
+        assert_eq!(history.latest().data[0].day, day!(1));
+    }
+
+    #[test]
+    fn parses_cpu_models() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: AuthenticAMD\nmodel name\t: AMD Ryzen 7 5800X 8-Core Processor\n\nprocessor\t: 1\nmodel name\t: AMD Ryzen 7 5800X 8-Core Processor\n";
+        assert_eq!(
+            parse_cpu_model(cpuinfo),
+            Some("AMD Ryzen 7 5800X 8-Core Processor".into())
+        );
+        assert_eq!(parse_cpu_model("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00 UTC");
+        assert_eq!(format_timestamp(1_733_652_000), "2024-12-08 10:00 UTC");
+        assert_eq!(format_timestamp(951_825_600), "2000-02-29 12:00 UTC");
+    }
+}
@@ -0,0 +1,380 @@
+/// Resolves which input a solution binary runs against, and how it is normalised.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use pico_args::Arguments;
+
+use crate::template::{config, Day, Error};
+
+/// Set by `cargo robust` for the variants of an input, which have to reach the solution unchanged.
+/// Disables the normalisation of inputs read by [`InputSource::read`].
+pub const RAW_INPUT_ENV: &str = "AOC_RAW_INPUT";
+
+/// The input a solution is run against.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input for the day, e.g. `data/inputs/01.txt`.
+    #[default]
+    Puzzle,
+    /// An example file for the day. A name selects a suffixed file, e.g. `data/examples/01-2.txt`.
+    Example(Option<String>),
+    /// An arbitrary file.
+    File(PathBuf),
+    /// Standard input.
+    Stdin,
+}
+
+impl InputSource {
+    /// Parses `--input <path>`, `--input -` and `--example [name]`.
+    /// The example name is taken from the next free argument, so other free arguments have to be parsed before.
+    pub fn from_args(args: &mut Arguments) -> Result<Self, String> {
+        let file: Option<String> = args
+            .opt_value_from_str("--input")
+            .map_err(|e| e.to_string())?;
+        let example = args.contains("--example");
+
+        match (file, example) {
+            (Some(_), true) => Err("`--input` and `--example` are mutually exclusive.".into()),
+            (Some(path), false) if path == "-" => Ok(Self::Stdin),
+            (Some(path), false) => Ok(Self::File(path.into())),
+            (None, true) => {
+                let name: Option<String> = args.opt_free_from_str().map_err(|e| e.to_string())?;
+                match name {
+                    Some(name) if name.starts_with('-') => {
+                        Err(format!("unexpected argument `{name}`."))
+                    }
+                    name => Ok(Self::Example(name)),
+                }
+            }
+            (None, false) => Ok(Self::Puzzle),
+        }
+    }
+
+    /// Converts the source back into arguments that can be passed to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::Example(None) => vec!["--example".into()],
+            Self::Example(Some(name)) => vec!["--example".into(), name.clone()],
+            Self::File(path) => vec!["--input".into(), path.to_string_lossy().into()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// Reads the contents of the source for a given day, normalised as configured for the day unless
+    /// [`RAW_INPUT_ENV`] is set.
+    pub fn read(&self, day: Day) -> Result<String, Error> {
+        let normalize = if env::var_os(RAW_INPUT_ENV).is_some() {
+            Normalize::RAW
+        } else {
+            config::get().normalize(day)
+        };
+        let bytes = self.read_bytes(day, normalize)?;
+        String::from_utf8(bytes).map_err(|_| Error::Failed(format!("{self} is not valid UTF-8.")))
+    }
+
+    /// Reads the contents of the source for a given day as bytes.
+    pub fn read_bytes(&self, day: Day, normalize: Normalize) -> Result<Vec<u8>, Error> {
+        let bytes = match self {
+            Self::Puzzle => return super::try_read_file_bytes("inputs", day, normalize),
+            Self::Example(None) => return super::try_read_file_bytes("examples", day, normalize),
+            Self::Example(Some(name)) => {
+                return super::read_data_file(
+                    &super::data_path("examples", &format!("{day}-{name}.txt")),
+                    "examples",
+                    day,
+                    normalize,
+                )
+            }
+            Self::File(path) => {
+                fs::read(path).map_err(|e| Error::io(format!("read \"{}\"", path.display()), e))?
+            }
+            Self::Stdin => {
+                let mut buf = Vec::new();
+                io::stdin()
+                    .read_to_end(&mut buf)
+                    .map_err(|e| Error::io("read stdin", e))?;
+                buf
+            }
+        };
+
+        normalize
+            .apply(bytes)
+            .map_err(|e| Error::Failed(format!("{self}: {e}")))
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Puzzle => write!(f, "puzzle input"),
+            Self::Example(None) => write!(f, "example"),
+            Self::Example(Some(name)) => write!(f, "example \"{name}\""),
+            Self::File(path) => write!(f, "\"{}\"", path.display()),
+            Self::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/// How the contents of an input are normalised before a solution sees them.
+///
+/// Configured per day in `aoc.toml` as a comma-separated list of options, e.g. `"crlf, trim"`, or `"raw"` for none.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Normalize {
+    /// Replaces `\r\n` line endings with `\n`. Option `crlf`.
+    pub crlf: bool,
+    /// Removes all line breaks at the end, so there are no empty lines after the last one. Option `trim`.
+    pub trim: bool,
+    /// Rejects tabs and non-ASCII bytes, e.g. from copying an example out of a browser. Option `ascii`.
+    pub ascii: bool,
+}
+
+impl Normalize {
+    /// Leaves the contents as they are.
+    pub const RAW: Self = Self {
+        crlf: false,
+        trim: false,
+        ascii: false,
+    };
+
+    /// Applies all options.
+    pub const STRICT: Self = Self {
+        crlf: true,
+        trim: true,
+        ascii: true,
+    };
+
+    /// Normalises a byte buffer in place.
+    pub fn apply(&self, mut input: Vec<u8>) -> Result<Vec<u8>, NormalizeError> {
+        if self.ascii {
+            check_ascii(&input)?;
+        }
+
+        if self.crlf {
+            let mut bytes = input.iter().copied().peekable();
+            let mut normalized = Vec::with_capacity(input.len());
+            while let Some(byte) = bytes.next() {
+                if byte != b'\r' || bytes.peek() != Some(&b'\n') {
+                    normalized.push(byte);
+                }
+            }
+            input = normalized;
+        }
+
+        if self.trim {
+            let len = input
+                .iter()
+                .rposition(|x| !matches!(x, b'\n' | b'\r'))
+                .map_or(0, |i| i + 1);
+            input.truncate(len);
+        }
+
+        Ok(input)
+    }
+
+    /// Normalises a string, e.g. an example embedded in a test.
+    pub fn apply_str(&self, input: &str) -> Result<String, NormalizeError> {
+        let bytes = self.apply(input.as_bytes().to_vec())?;
+        // only ASCII line breaks are removed, so the contents remain valid UTF-8.
+        Ok(String::from_utf8(bytes).expect("normalized input is valid UTF-8"))
+    }
+}
+
+impl FromStr for Normalize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut normalize = Self::RAW;
+
+        for option in s.split(',').map(str::trim) {
+            match option {
+                "raw" => {}
+                "strict" => normalize = Self::STRICT,
+                "crlf" => normalize.crlf = true,
+                "trim" => normalize.trim = true,
+                "ascii" => normalize.ascii = true,
+                x => {
+                    return Err(format!(
+                        "expecting `raw`, `strict` or a list of `crlf`, `trim` and `ascii`, found `{x}`"
+                    ))
+                }
+            }
+        }
+
+        Ok(normalize)
+    }
+}
+
+fn check_ascii(input: &[u8]) -> Result<(), NormalizeError> {
+    let Some(i) = input.iter().position(|x| *x == b'\t' || !x.is_ascii()) else {
+        return Ok(());
+    };
+
+    let line_start = input[..i]
+        .iter()
+        .rposition(|x| *x == b'\n')
+        .map_or(0, |x| x + 1);
+
+    Err(NormalizeError {
+        line: input[..i].iter().filter(|x| **x == b'\n').count() + 1,
+        column: i - line_start + 1,
+        byte: input[i],
+    })
+}
+
+/// An input contains a byte rejected by [`Normalize::ascii`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct NormalizeError {
+    pub line: usize,
+    pub column: usize,
+    pub byte: u8,
+}
+
+impl std::error::Error for NormalizeError {}
+
+impl Display for NormalizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self { line, column, byte } = self;
+        match byte {
+            b'\t' => write!(f, "unexpected tab at line {line}, column {column}."),
+            _ => write!(
+                f,
+                "unexpected non-ASCII byte 0x{byte:02x} at line {line}, column {column}."
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use pico_args::Arguments;
+
+    use super::{InputSource, Normalize, NormalizeError};
+
+    fn parse(s: &str) -> Result<InputSource, String> {
+        InputSource::from_args(&mut Arguments::from_vec(
+            s.split_whitespace().map(Into::into).collect(),
+        ))
+    }
+
+    #[test]
+    fn defaults_to_puzzle_input() {
+        assert_eq!(parse("--time"), Ok(InputSource::Puzzle));
+    }
+
+    #[test]
+    fn parses_input_file() {
+        assert_eq!(
+            parse("--input foo/bar.txt"),
+            Ok(InputSource::File("foo/bar.txt".into()))
+        );
+    }
+
+    #[test]
+    fn parses_stdin() {
+        assert_eq!(parse("--input -"), Ok(InputSource::Stdin));
+    }
+
+    #[test]
+    fn parses_examples() {
+        assert_eq!(parse("--example"), Ok(InputSource::Example(None)));
+        assert_eq!(
+            parse("--example 2"),
+            Ok(InputSource::Example(Some("2".into())))
+        );
+    }
+
+    #[test]
+    fn round_trips_arguments() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Example(None),
+            InputSource::Example(Some("large".into())),
+            InputSource::File("foo.txt".into()),
+            InputSource::Stdin,
+        ] {
+            let args = source.to_args().into_iter().map(Into::into).collect();
+            assert_eq!(
+                InputSource::from_args(&mut Arguments::from_vec(args)),
+                Ok(source)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_conflicting_sources() {
+        assert!(parse("--input - --example").is_err());
+        assert!(parse("--input").is_err());
+        assert!(parse("--example --time").is_err());
+    }
+
+    #[test]
+    fn normalizes_line_endings() {
+        let normalize = Normalize {
+            crlf: true,
+            ..Normalize::RAW
+        };
+        assert_eq!(
+            normalize.apply_str("1 2\r\n3\r4\r\n"),
+            Ok("1 2\n3\r4\n".into())
+        );
+        assert_eq!(Normalize::RAW.apply_str("1\r\n"), Ok("1\r\n".into()));
+    }
+
+    #[test]
+    fn trims_trailing_line_breaks() {
+        let normalize = Normalize {
+            trim: true,
+            ..Normalize::RAW
+        };
+        assert_eq!(normalize.apply_str("1\n\n2\r\n\n"), Ok("1\n\n2".into()));
+        assert_eq!(normalize.apply_str("\n\n"), Ok("".into()));
+        assert_eq!(
+            normalize.apply(b"#.\n.#\n".to_vec()),
+            Ok(b"#.\n.#".to_vec())
+        );
+    }
+
+    #[test]
+    fn rejects_tabs_and_non_ascii() {
+        assert_eq!(
+            Normalize::STRICT.apply_str("1 2\n3\t4\n"),
+            Err(NormalizeError {
+                line: 2,
+                column: 2,
+                byte: b'\t'
+            })
+        );
+        assert_eq!(
+            Normalize::STRICT
+                .apply_str("1 2\n3 4 \u{2212}5")
+                .unwrap_err()
+                .to_string(),
+            "unexpected non-ASCII byte 0xe2 at line 2, column 5."
+        );
+        assert_eq!(Normalize::STRICT.apply_str("1 2\r\n"), Ok("1 2".into()));
+    }
+
+    #[test]
+    fn parses_options() {
+        assert_eq!("raw".parse(), Ok(Normalize::RAW));
+        assert_eq!("strict".parse(), Ok(Normalize::STRICT));
+        assert_eq!(
+            "crlf, trim".parse(),
+            Ok(Normalize {
+                crlf: true,
+                trim: true,
+                ascii: false
+            })
+        );
+        assert!("crlf, tabs".parse::<Normalize>().is_err());
+    }
+}
@@ -1,39 +1,128 @@
-use std::{env, fs};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod config;
+pub mod input;
+pub mod ocr;
+pub mod output;
+pub mod profile;
+pub mod report;
 pub mod runner;
+pub mod solution_args;
+pub mod submissions;
 
 pub use day::*;
+pub use day_set::{DaySet, DaySetFromStrError, DaySetResolveError};
+pub use error::Error;
+pub use input::Normalize;
+pub use solution_error::{SolutionError, SolutionResult};
 
+mod answers;
+mod budget;
+mod compare;
 mod day;
+mod day_set;
+mod error;
+mod history;
+mod plausibility;
 mod readme_benchmarks;
 mod run_multi;
+mod solution_error;
+mod stats;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+fn data_path(folder: &str, file: &str) -> PathBuf {
+    config::get().data_path(folder, file)
+}
+
+/// Helper function that reads a text file to a string, panicking with a descriptive message if it is missing.
+/// Inputs are read from the active profile's input if a profile is set.
+/// The contents are normalised as configured for the day, see [`Normalize`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file(folder, day).unwrap_or_else(|e| panic!("{e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file_part(folder, day, part).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads a text file of a day from the data directory, e.g. `data/examples/01.txt`,
+/// normalised as configured for the day.
+pub fn try_read_file(folder: &str, day: Day) -> Result<String, Error> {
+    try_read_file_with(folder, day, config::get().normalize(day))
+}
+
+/// Reads a text file of a day from the data directory with explicit normalisation options.
+pub fn try_read_file_with(folder: &str, day: Day, normalize: Normalize) -> Result<String, Error> {
+    let path = day_path(folder, day);
+    into_string(&path, read_data_file(&path, folder, day, normalize)?)
+}
+
+/// Reads a file of a day from the data directory as bytes, e.g. for solutions working on byte grids.
+pub fn try_read_file_bytes(folder: &str, day: Day, normalize: Normalize) -> Result<Vec<u8>, Error> {
+    read_data_file(&day_path(folder, day), folder, day, normalize)
+}
+
+/// Reads a text file of a day from the data directory, appending a part suffix. E.g. like `01-2.txt`.
+pub fn try_read_file_part(folder: &str, day: Day, part: u8) -> Result<String, Error> {
+    let path = data_path(folder, &format!("{day}-{part}.txt"));
+    into_string(
+        &path,
+        read_data_file(&path, folder, day, config::get().normalize(day))?,
+    )
+}
+
+fn day_path(folder: &str, day: Day) -> PathBuf {
+    match folder {
+        "inputs" => profile::get_input_path(day),
+        _ => data_path(folder, &format!("{day}.txt")),
+    }
+}
+
+/// Reads and normalises a file from the data directory, suggesting how to create it if it is missing.
+fn read_data_file(
+    path: &Path,
+    folder: &str,
+    day: Day,
+    normalize: Normalize,
+) -> Result<Vec<u8>, Error> {
+    let bytes = fs::read(path).map_err(|e| {
+        if e.kind() != io::ErrorKind::NotFound {
+            return Error::io(format!("read \"{}\"", path.display()), e);
+        }
+
+        let hint = match folder {
+            "inputs" => format!("Run `cargo download {day}` to download the input."),
+            "examples" => "Create it and paste the example from the puzzle description.".into(),
+            "puzzles" => format!("Run `cargo download {day}` to download the puzzle."),
+            _ => "Create it first.".into(),
+        };
+
+        Error::MissingFile {
+            path: path.to_path_buf(),
+            hint,
+        }
+    })?;
+
+    normalize
+        .apply(bytes)
+        .map_err(|e| Error::Failed(format!("\"{}\": {e}", path.display())))
+}
+
+fn into_string(path: &Path, bytes: Vec<u8>) -> Result<String, Error> {
+    String::from_utf8(bytes)
+        .map_err(|_| Error::Failed(format!("\"{}\" is not valid UTF-8.", path.display())))
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
@@ -61,7 +150,8 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
+            perturb_threads();
+            let input = read_input(DAY);
             $( run_part($func, &input, DAY, $part); )*
         }
     };
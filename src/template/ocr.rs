@@ -0,0 +1,300 @@
+/// Recognises the letter art that some puzzles produce as answers, e.g.
+///
+/// ```text
+/// #..#.###.
+/// #..#..#..
+/// ####..#..
+/// #..#..#..
+/// #..#..#..
+/// #..#.###.
+/// ```
+///
+/// Both fonts used by AoC are supported: the small font that is 6 pixels high and the large font that is 10 pixels high.
+/// Glyphs are separated by columns without lit pixels.
+use std::fmt::Display;
+
+/// Glyphs of the small font, 6 pixels high.
+const SMALL_FONT: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Glyphs of the large font, 10 pixels high.
+const LARGE_FONT: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// An error which can be returned when recognising letter art.
+#[derive(Debug, PartialEq, Eq)]
+pub enum OcrError {
+    /// The art contains no lit pixels.
+    Empty,
+    /// The art contains a character that is neither the `on` nor the `off` character.
+    UnexpectedCharacter(char),
+    /// Glyphs that do not match any letter, with their position and rendering.
+    UnrecognisedGlyphs(Vec<(usize, String)>),
+}
+
+impl std::error::Error for OcrError {}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Empty => write!(f, "letter art does not contain any lit pixels."),
+            OcrError::UnexpectedCharacter(c) => {
+                write!(f, "unexpected character `{c}` in letter art.")
+            }
+            OcrError::UnrecognisedGlyphs(glyphs) => {
+                write!(f, "could not recognise {} glyph(s):", glyphs.len())?;
+                for (position, glyph) in glyphs {
+                    write!(f, "\n\nglyph {}:\n{glyph}", position + 1)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Recognises letter art that uses `#` for lit pixels and `.` or spaces for dark pixels.
+pub fn recognise(art: &str) -> Result<String, OcrError> {
+    recognise_pixels(art, |c| match c {
+        '#' | '█' => Ok(true),
+        '.' | ' ' => Ok(false),
+        c => Err(OcrError::UnexpectedCharacter(c)),
+    })
+}
+
+/// Recognises letter art that uses custom characters for lit and dark pixels.
+pub fn recognise_with(art: &str, on: char, off: char) -> Result<String, OcrError> {
+    recognise_pixels(art, |c| match c {
+        c if c == on => Ok(true),
+        c if c == off => Ok(false),
+        c => Err(OcrError::UnexpectedCharacter(c)),
+    })
+}
+
+fn recognise_pixels(
+    art: &str,
+    is_on: impl Fn(char) -> Result<bool, OcrError>,
+) -> Result<String, OcrError> {
+    let mut rows: Vec<Vec<bool>> = art
+        .lines()
+        .map(|line| line.chars().map(&is_on).collect())
+        .collect::<Result<_, _>>()?;
+
+    // ignore blank rows around the letters.
+    while rows.last().is_some_and(|row| !row.contains(&true)) {
+        rows.pop();
+    }
+    let first_lit = rows.iter().position(|row| row.contains(&true));
+    let Some(first_lit) = first_lit else {
+        return Err(OcrError::Empty);
+    };
+    rows.drain(..first_lit);
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or_default();
+    let is_lit = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or_default();
+
+    let mut letters = String::new();
+    let mut unrecognised = vec![];
+    let mut x = 0;
+
+    while x < width {
+        if !(0..rows.len()).any(|y| is_lit(x, y)) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && (0..rows.len()).any(|y| is_lit(x, y)) {
+            x += 1;
+        }
+
+        let glyph = (0..rows.len())
+            .map(|y| {
+                (start..x)
+                    .map(|x| if is_lit(x, y) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        match find_letter(&glyph) {
+            Some(letter) => letters.push(letter),
+            None => {
+                unrecognised.push((letters.len(), glyph));
+                letters.push('?');
+            }
+        }
+    }
+
+    if unrecognised.is_empty() {
+        Ok(letters)
+    } else {
+        Err(OcrError::UnrecognisedGlyphs(unrecognised))
+    }
+}
+
+fn find_letter(glyph: &str) -> Option<char> {
+    SMALL_FONT
+        .iter()
+        .chain(LARGE_FONT)
+        .find(|(_, pattern)| trim_glyph(pattern) == glyph)
+        .map(|(letter, _)| *letter)
+}
+
+/// Removes dark columns on both sides of a glyph, as glyphs are split at dark columns.
+fn trim_glyph(pattern: &str) -> String {
+    let rows: Vec<&str> = pattern.lines().collect();
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or_default();
+    let is_dark = |x: usize| rows.iter().all(|r| r.as_bytes().get(x) != Some(&b'#'));
+
+    let start = (0..width).find(|&x| !is_dark(x)).unwrap_or_default();
+    let end = (0..width).rfind(|&x| !is_dark(x)).map_or(start, |x| x + 1);
+
+    rows.iter()
+        .map(|r| &r[start..end])
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{recognise, recognise_with, OcrError};
+
+    #[test]
+    fn recognises_small_font() {
+        let art = [
+            "#..#.###..####.",
+            "#..#..#...#....",
+            "####..#...###..",
+            "#..#..#...#....",
+            "#..#..#...#....",
+            "#..#.###..####.",
+        ]
+        .join("\n");
+        assert_eq!(recognise(&art), Ok("HIE".into()));
+    }
+
+    #[test]
+    fn recognises_large_font() {
+        let art = [
+            "#....#..######",
+            "#....#.......#",
+            ".#..#........#",
+            ".#..#.......#.",
+            "..##.......#..",
+            "..##......#...",
+            ".#..#....#....",
+            ".#..#...#.....",
+            "#....#..#.....",
+            "#....#..######",
+        ]
+        .join("\n");
+        assert_eq!(recognise(&art), Ok("XZ".into()));
+    }
+
+    #[test]
+    fn ignores_blank_rows_and_columns() {
+        let art = "\n      \n  ##  \n #  # \n #  # \n #### \n #  # \n #  # \n\n";
+        assert_eq!(recognise(art), Ok("A".into()));
+    }
+
+    #[test]
+    fn uses_custom_characters() {
+        let art = ["xxxx", "x___", "xxx_", "x___", "x___", "x___"].join("\n");
+        assert_eq!(recognise_with(&art, 'x', '_'), Ok("F".into()));
+        assert_eq!(recognise(&art), Err(OcrError::UnexpectedCharacter('x')));
+    }
+
+    #[test]
+    fn lists_unrecognised_glyphs() {
+        let art = ["#..#.#", "#..#.#", "####.#", "#..#..", "#..#.#", "#..#.#"].join("\n");
+        let Err(OcrError::UnrecognisedGlyphs(glyphs)) = recognise(&art) else {
+            panic!("expected unrecognised glyphs");
+        };
+        assert_eq!(glyphs, vec![(1, "#\n#\n#\n.\n#\n#".to_string())]);
+    }
+
+    #[test]
+    fn errors_on_empty_art() {
+        assert_eq!(recognise("....\n...."), Err(OcrError::Empty));
+    }
+}
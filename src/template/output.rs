@@ -0,0 +1,287 @@
+/// Output layer shared by the solution binaries and the commands of the CLI.
+///
+/// Output goes through a [`Reporter`], which is selected once per process: either explicitly with
+/// `--format <pretty|plain|quiet>`, or automatically. Escape sequences and progress that overwrites
+/// itself are only used if stdout is a terminal and [`NO_COLOR`](https://no-color.org) is not set.
+use std::{
+    env,
+    ffi::OsString,
+    fmt::Display,
+    io::{stdout, IsTerminal, Write},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// The answer of a part as printed by a reporter: `Ok(None)` if it is not implemented, `Err` if it failed.
+pub type PartAnswer = Result<Option<String>, String>;
+
+/// How results are printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Highlighted answers, with progress shown while a part runs.
+    Pretty,
+    /// One line per result without escape sequences, e.g. for logs or piping into other tools.
+    Plain,
+    /// Only the answers, one per line.
+    Quiet,
+}
+
+impl OutputFormat {
+    /// Selects the pretty format for terminals, unless colors are disabled with a non-empty `NO_COLOR`.
+    pub fn detect(is_terminal: bool, no_color: Option<OsString>) -> Self {
+        if is_terminal && no_color.is_none_or(|x| x.is_empty()) {
+            Self::Pretty
+        } else {
+            Self::Plain
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pretty" => Ok(Self::Pretty),
+            "plain" => Ok(Self::Plain),
+            "quiet" => Ok(Self::Quiet),
+            x => Err(format!(
+                "expecting `pretty`, `plain` or `quiet`, found `{x}`."
+            )),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pretty => write!(f, "pretty"),
+            Self::Plain => write!(f, "plain"),
+            Self::Quiet => write!(f, "quiet"),
+        }
+    }
+}
+
+static FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+
+/// Selects the format of this process. Has no effect once output has been printed.
+pub fn set_format(format: OutputFormat) {
+    let _ = FORMAT.set(format);
+}
+
+/// Returns the format of this process, detecting it on first use if none was set.
+pub fn format() -> OutputFormat {
+    *FORMAT.get_or_init(|| OutputFormat::detect(stdout().is_terminal(), env::var_os("NO_COLOR")))
+}
+
+/// Returns the reporter for the format of this process.
+pub fn reporter() -> &'static dyn Reporter {
+    match format() {
+        OutputFormat::Pretty => &Pretty,
+        OutputFormat::Plain => &Plain,
+        OutputFormat::Quiet => &Quiet,
+    }
+}
+
+/// Prints the output of commands and solutions. The default methods print plain text.
+pub trait Reporter: Sync {
+    /// A heading, e.g. the day above its results.
+    fn heading(&self, text: &str) {
+        println!("{text}");
+    }
+
+    /// Secondary information, e.g. the input a solution reads.
+    fn info(&self, text: &str) {
+        println!("{text}");
+    }
+
+    fn warning(&self, text: &str) {
+        eprintln!("{text}");
+    }
+
+    /// Highlights text within a line, e.g. an answer.
+    fn strong(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    /// De-emphasises text within a line, e.g. a duration.
+    fn subtle(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    /// Shown as soon as a part returned, before it is benched.
+    fn intermediate_result(&self, _part: u8, _answer: &PartAnswer) {}
+
+    /// Shown while a part is benched.
+    fn bench_started(&self) {}
+
+    /// The final result of a part. `duration` is empty or a formatted duration with a leading space.
+    fn result(&self, part: u8, answer: &PartAnswer, duration: &str) {
+        println!(
+            "{}",
+            format_result(part, answer, duration, |x| x.to_string())
+        );
+    }
+}
+
+/// Prints rows as a table with left-aligned columns and a highlighted header.
+pub fn print_table(header: &[String], rows: &[Vec<String>]) {
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(Vec::as_slice)
+                .chain([header])
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let format_row = |row: &[String]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", reporter().strong(&format_row(header)));
+    for row in rows {
+        println!("{}", format_row(row));
+    }
+}
+
+/// Formats a result line like `Part 1: 42 (1.2ms)`, which is parsed by the commands running solutions.
+pub(crate) fn format_result(
+    part: u8,
+    answer: &PartAnswer,
+    duration: &str,
+    strong: impl Fn(&str) -> String,
+) -> String {
+    match answer {
+        Ok(Some(answer)) if answer.contains('\n') => format!("Part {part}: ▼{duration}\n{answer}"),
+        Ok(Some(answer)) => format!("Part {part}: {}{duration}", strong(answer)),
+        Ok(None) => format!("Part {part}: ✖"),
+        Err(e) => format!("Part {part}: ✖ {e}"),
+    }
+}
+
+/// Colors and in-place progress for terminals.
+pub struct Pretty;
+
+impl Reporter for Pretty {
+    fn heading(&self, text: &str) {
+        println!("{}", self.strong(text));
+    }
+
+    fn info(&self, text: &str) {
+        println!("{}", self.subtle(text));
+    }
+
+    fn warning(&self, text: &str) {
+        eprintln!("{}", self.subtle(text));
+    }
+
+    fn strong(&self, text: &str) -> String {
+        format!("{ANSI_BOLD}{text}{ANSI_RESET}")
+    }
+
+    fn subtle(&self, text: &str) -> String {
+        format!("{ANSI_ITALIC}{text}{ANSI_RESET}")
+    }
+
+    fn intermediate_result(&self, part: u8, answer: &PartAnswer) {
+        let line = match answer {
+            Ok(Some(answer)) if !answer.contains('\n') => {
+                format!("Part {part}: {}", self.strong(answer))
+            }
+            Ok(Some(_)) => format!("Part {part}: ▼"),
+            _ => format!("Part {part}: ✖"),
+        };
+        print!("{line}");
+        let _ = stdout().flush();
+    }
+
+    fn bench_started(&self) {
+        print!(" > {}", self.subtle("benching"));
+        let _ = stdout().flush();
+    }
+
+    fn result(&self, part: u8, answer: &PartAnswer, duration: &str) {
+        // return to the start of the line and clear it, replacing the intermediate result.
+        let line = format_result(part, answer, duration, |x| self.strong(x));
+        println!("\r\x1b[2K{line}");
+    }
+}
+
+/// Plain text without escape sequences.
+pub struct Plain;
+
+impl Reporter for Plain {}
+
+/// Answers only, errors are printed to stderr.
+pub struct Quiet;
+
+impl Reporter for Quiet {
+    fn heading(&self, _text: &str) {}
+
+    fn info(&self, _text: &str) {}
+
+    fn result(&self, part: u8, answer: &PartAnswer, _duration: &str) {
+        match answer {
+            Ok(Some(answer)) => println!("{answer}"),
+            Ok(None) => {}
+            Err(_) => eprintln!("{}", format_result(part, answer, "", |x| x.to_string())),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_result, OutputFormat};
+
+    #[test]
+    fn detects_format() {
+        assert_eq!(OutputFormat::detect(true, None), OutputFormat::Pretty);
+        assert_eq!(OutputFormat::detect(false, None), OutputFormat::Plain);
+        assert_eq!(
+            OutputFormat::detect(true, Some("1".into())),
+            OutputFormat::Plain
+        );
+        // an empty `NO_COLOR` does not disable colors.
+        assert_eq!(
+            OutputFormat::detect(true, Some("".into())),
+            OutputFormat::Pretty
+        );
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("quiet".parse(), Ok(OutputFormat::Quiet));
+        assert!("fancy".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn formats_results() {
+        let plain = |x: &str| x.to_string();
+        assert_eq!(
+            format_result(1, &Ok(Some("42".into())), " (1.2ms)", plain),
+            "Part 1: 42 (1.2ms)"
+        );
+        assert_eq!(
+            format_result(2, &Ok(Some("#.\n.#".into())), " (1.2ms)", plain),
+            "Part 2: ▼ (1.2ms)\n#.\n.#"
+        );
+        assert_eq!(format_result(1, &Ok(None), "", plain), "Part 1: ✖");
+        assert_eq!(
+            format_result(1, &Err("no path".into()), "", plain),
+            "Part 1: ✖ no path"
+        );
+    }
+}
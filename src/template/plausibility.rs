@@ -0,0 +1,123 @@
+/// Heuristics that flag answers which are likely wrong, e.g. because of an integer overflow or an empty input.
+use std::fmt::Display;
+
+/// Relative distance to an integer type's maximum that is considered suspicious.
+const NEAR_MAX_RATIO: f64 = 0.01;
+
+/// Maximum values of the integer types that answers commonly overflow.
+const INTEGER_MAXIMA: &[(&str, u128)] = &[
+    ("i32", i32::MAX as u128),
+    ("u32", u32::MAX as u128),
+    ("i64", i64::MAX as u128),
+    ("u64", u64::MAX as u128),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Warning {
+    Zero,
+    Negative,
+    NearIntegerMax(&'static str),
+    EqualsExampleAnswer,
+    EqualsOtherPart(u8),
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::Zero => write!(f, "answer is zero."),
+            Warning::Negative => write!(f, "answer is negative."),
+            Warning::NearIntegerMax(ty) => {
+                write!(f, "answer is close to `{ty}::MAX`, check for an overflow.")
+            }
+            Warning::EqualsExampleAnswer => write!(f, "answer is the same as the example answer."),
+            Warning::EqualsOtherPart(part) => {
+                write!(f, "answer is the same as the answer of part {part}.")
+            }
+        }
+    }
+}
+
+/// Runs all plausibility checks for an answer.
+/// `other_answers` holds the answers of the other part, if known.
+pub fn check(
+    answer: &str,
+    example_answer: Option<&str>,
+    other_answers: &[(u8, &str)],
+) -> Vec<Warning> {
+    let mut warnings = vec![];
+
+    if let Ok(n) = answer.parse::<i128>() {
+        if n == 0 {
+            warnings.push(Warning::Zero);
+        } else if n < 0 {
+            warnings.push(Warning::Negative);
+        }
+    }
+
+    if let Ok(n) = answer.parse::<u128>() {
+        #[allow(clippy::cast_precision_loss)]
+        let near_max = INTEGER_MAXIMA
+            .iter()
+            .find(|(_, max)| n <= *max && (*max - n) as f64 <= *max as f64 * NEAR_MAX_RATIO);
+
+        if let Some((ty, _)) = near_max {
+            warnings.push(Warning::NearIntegerMax(ty));
+        }
+    }
+
+    if example_answer.is_some_and(|x| x == answer) {
+        warnings.push(Warning::EqualsExampleAnswer);
+    }
+
+    for (part, other) in other_answers {
+        if *other == answer {
+            warnings.push(Warning::EqualsOtherPart(*part));
+        }
+    }
+
+    warnings
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, Warning};
+
+    #[test]
+    fn accepts_plausible_answers() {
+        assert_eq!(check("1651298", Some("11"), &[(1, "42")]), vec![]);
+        assert_eq!(check("ABCDEF", None, &[]), vec![]);
+    }
+
+    #[test]
+    fn flags_zero_and_negative_answers() {
+        assert_eq!(check("0", None, &[]), vec![Warning::Zero]);
+        assert_eq!(check("-12", None, &[]), vec![Warning::Negative]);
+    }
+
+    #[test]
+    fn flags_answers_near_integer_maxima() {
+        assert_eq!(
+            check("4294967295", None, &[]),
+            vec![Warning::NearIntegerMax("u32")]
+        );
+        assert_eq!(
+            check("2147483000", None, &[]),
+            vec![Warning::NearIntegerMax("i32")]
+        );
+        assert_eq!(
+            check("18446744073709551615", None, &[]),
+            vec![Warning::NearIntegerMax("u64")]
+        );
+        assert_eq!(check("4000000000", None, &[]), vec![]);
+    }
+
+    #[test]
+    fn flags_repeated_answers() {
+        assert_eq!(
+            check("31", Some("31"), &[(1, "31")]),
+            vec![Warning::EqualsExampleAnswer, Warning::EqualsOtherPart(1)]
+        );
+    }
+}
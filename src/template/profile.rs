@@ -0,0 +1,91 @@
+/// Named account profiles, so one repository can hold the inputs and answers of several AoC accounts.
+///
+/// The active profile is read from the `AOC_PROFILE` environment variable, which the `--profile` option sets for
+/// all child processes. Without a profile, inputs live at `data/inputs/<day>.txt`. With a profile, they live in
+/// the day's input corpus at `data/inputs/<day>/<profile>.txt`.
+use std::{env, path::PathBuf};
+
+use crate::template::{config, Day};
+
+pub const PROFILE_ENV: &str = "AOC_PROFILE";
+
+/// Returns the name of the active profile, if any.
+///
+/// # Panics
+/// If the name is invalid, as paths built from it could escape the data directory. Commands and solutions check
+/// it upfront with [`validate_active`].
+pub fn active_profile() -> Option<String> {
+    let name = env::var(PROFILE_ENV).ok().filter(|x| !x.is_empty())?;
+
+    if let Err(e) = validate_name(&name) {
+        panic!("{PROFILE_ENV}: {e}");
+    }
+
+    Some(name)
+}
+
+/// Checks the name of the active profile, which can be set in the environment directly instead of with `--profile`.
+pub fn validate_active() -> Result<(), String> {
+    match env::var(PROFILE_ENV) {
+        Ok(name) if !name.is_empty() => {
+            validate_name(&name).map_err(|e| format!("{PROFILE_ENV}: {e}"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that a profile name can safely be used as part of a file name.
+pub fn validate_name(name: &str) -> Result<(), String> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid profile name `{name}`, expecting letters, digits, `-` or `_`."
+        ))
+    }
+}
+
+/// Returns the input path for a day, respecting the active profile.
+pub fn get_input_path(day: Day) -> PathBuf {
+    match active_profile() {
+        Some(profile) => config::get().data_path("inputs", &format!("{day}/{profile}.txt")),
+        None => config::get().data_path("inputs", &format!("{day}.txt")),
+    }
+}
+
+/// Returns the puzzle description path for a day, respecting the active profile.
+pub fn get_puzzle_path(day: Day) -> PathBuf {
+    match active_profile() {
+        Some(profile) => config::get().data_path("puzzles", &format!("{day}/{profile}.md")),
+        None => config::get().data_path("puzzles", &format!("{day}.md")),
+    }
+}
+
+/// Returns the aoc-cli session file of the active profile, e.g. `~/.adventofcode.alice.session`.
+/// Without a profile, aoc-cli falls back to its default session file.
+pub fn get_session_file() -> Option<PathBuf> {
+    let profile = active_profile()?;
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    Some(PathBuf::from(home).join(format!(".adventofcode.{profile}.session")))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::validate_name;
+
+    #[test]
+    fn validates_profile_names() {
+        assert!(validate_name("alice").is_ok());
+        assert!(validate_name("work_2-b").is_ok());
+
+        for name in ["", "../../x", "a/b", "a.b", "~alice"] {
+            assert!(validate_name(name).is_err(), "{name}");
+        }
+    }
+}
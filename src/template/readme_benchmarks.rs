@@ -2,8 +2,10 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::budget::{self, BudgetUsage};
+use crate::template::config::BudgetConfig;
+use crate::template::timings::{PartTiming, Timings};
+use crate::template::{config, Day};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -52,76 +54,145 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+/// Formats how much of a budget is used for the table, e.g. `62% of 50ms`, marking overruns.
+fn format_usage(usage: &BudgetUsage) -> String {
+    if usage.is_overrun() {
+        format!("⚠️ {}", budget::format_usage(usage))
+    } else {
+        budget::format_usage(usage)
+    }
+}
+
+fn format_part(part: Option<PartTiming>, usage: Option<&BudgetUsage>) -> String {
+    let Some(part) = part else {
+        return "`-`".into();
+    };
+
+    match usage {
+        Some(usage) => format!("`{part}` ({})", format_usage(usage)),
+        None => format!("`{part}`"),
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Timings,
+    total_millis: f64,
+    budget: Option<&BudgetConfig>,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
 
-    for timing in timings.data {
+    if budget.is_some() {
+        lines.push("| Day | Part 1 | Part 2 | Budget |".into());
+        lines.push("| :---: | :---: | :---:  | :---: |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
+
+    for timing in &timings.data {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+        let usages = budget.map(|x| budget::day_usages(x, timing));
+        let usage = |i: usize| usages.as_ref().and_then(|x| x[i].as_ref());
+
+        let mut line = format!(
+            "| [Day {}]({}) | {} | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            format_part(timing.part_1, usage(1)),
+            format_part(timing.part_2, usage(2))
+        );
+
+        if budget.is_some() {
+            let day_usage = usage(0).map_or_else(|| "-".into(), format_usage);
+            line.push_str(&format!(" {day_usage} |"));
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    match budget.and_then(|x| budget::total_usage(x, &timings)) {
+        Some(usage) => lines.push(format!(
+            "**Total: {total_millis:.2}ms** ({})",
+            format_usage(&usage)
+        )),
+        None => lines.push(format!("**Total: {total_millis:.2}ms**")),
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    budget: Option<&BudgetConfig>,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", timings, total_millis, budget);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
+    let config = config::get();
+    let path = &config.readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let budget = Some(&config.budget).filter(|x| x.show_in_readme && !x.is_empty());
+    update_content(&mut readme, timings, total_millis, budget)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
+    use std::{collections::BTreeMap, time::Duration};
+
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day,
+        template::config::{BudgetConfig, DayBudget},
+        template::timings::{PartTiming, Timing, Timings},
+    };
+
+    fn millis(millis: f64) -> PartTiming {
+        PartTiming {
+            nanos: millis * 1_000_000.0,
+            samples: Some(100),
+            std_dev: None,
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(millis(10.0)),
+                    part_2: Some(millis(20.0)),
                     total_nanos: 3e+10,
+                    status: Default::default(),
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(millis(30.0)),
+                    part_2: Some(millis(40.0)),
                     total_nanos: 7e+10,
+                    status: Default::default(),
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    part_1: Some(millis(40.0)),
+                    part_2: Some(millis(50.0)),
                     total_nanos: 9e+10,
+                    status: Default::default(),
                 },
             ],
         }
@@ -131,28 +202,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +231,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -169,9 +240,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +251,44 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn formats_budget_usage() {
+        // the total durations of the mock timings are in seconds.
+        let budget = BudgetConfig {
+            total: Some(Duration::from_secs(200)),
+            days: BTreeMap::from([
+                (
+                    day!(1),
+                    DayBudget {
+                        total: Some(Duration::from_secs(40)),
+                        parts: [Some(Duration::from_millis(5)), None],
+                    },
+                ),
+                (
+                    day!(2),
+                    DayBudget {
+                        total: Some(Duration::from_secs(100)),
+                        parts: [None, None],
+                    },
+                ),
+            ]),
+            show_in_readme: true,
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, Some(&budget)).unwrap();
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(
+            lines[3..=7],
+            [
+                "| Day | Part 1 | Part 2 | Budget |",
+                "| :---: | :---: | :---:  | :---: |",
+                "| [Day 1](./src/bin/01.rs) | `10.0ms` (⚠️ 200% of 5ms) | `20.0ms` | 75% of 40s |",
+                "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` | 70% of 100s |",
+                "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` | - |",
+            ]
+        );
+        assert_eq!(lines[9], "**Total: 190.00ms** (95% of 200s)");
+    }
 }
@@ -0,0 +1,421 @@
+/// Machine-readable reports of `cargo all`, with one test case per day and part.
+/// Supported formats are JUnit XML and TAP, e.g. to display results in the test UI of a CI service.
+use std::{fmt::Write, fs, io, path::PathBuf, str::FromStr, time::Duration};
+
+use crate::template::{
+    run_multi::{DayRun, DayStatus},
+    timings::PartStatus,
+    Day,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Junit,
+    Tap,
+}
+
+/// A report requested with `--report <format>=<path>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReportTarget {
+    pub format: ReportFormat,
+    pub path: PathBuf,
+}
+
+impl FromStr for ReportTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((format, path)) = s.split_once('=').filter(|(_, path)| !path.is_empty()) else {
+            return Err(format!(
+                "expecting `junit=<path>` or `tap=<path>`, found `{s}`."
+            ));
+        };
+
+        let format = match format {
+            "junit" => ReportFormat::Junit,
+            "tap" => ReportFormat::Tap,
+            x => {
+                return Err(format!(
+                    "unknown report format `{x}`, expecting `junit` or `tap`."
+                ))
+            }
+        };
+
+        Ok(ReportTarget {
+            format,
+            path: path.into(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    Failed(String),
+    Skipped(String),
+}
+
+/// The result of a single part of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestCase {
+    pub day: Day,
+    pub part: u8,
+    pub outcome: Outcome,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    pub duration: Option<Duration>,
+}
+
+impl TestCase {
+    fn name(&self) -> String {
+        format!("Day {} Part {}", self.day, self.part)
+    }
+}
+
+/// Converts the runs of `cargo all` to test cases. Answers are only compared if the runs were checked.
+pub fn collect_test_cases(runs: &[DayRun]) -> Vec<TestCase> {
+    runs.iter()
+        .flat_map(|run| (1..=2).map(move |part| get_test_case(run, part)))
+        .collect()
+}
+
+fn get_test_case(run: &DayRun, part: u8) -> TestCase {
+    let output = run.parts.iter().find(|p| p.part == part);
+    let expected = run
+        .expected
+        .as_ref()
+        .and_then(|x| x.get(part))
+        .map(String::from);
+
+    let outcome = match (output, run.status) {
+        (_, DayStatus::NotScaffolded) => Outcome::Skipped("not scaffolded".into()),
+        (_, DayStatus::BuildFailed) => Outcome::Failed("build failed".into()),
+        (None, DayStatus::TimedOut) => Outcome::Failed("timed out".into()),
+        (None, DayStatus::Failed | DayStatus::Panicked) => Outcome::Failed(
+            run.error
+                .clone()
+                .unwrap_or_else(|| "exited with a non-zero status".into()),
+        ),
+        (None, _) => Outcome::Skipped("not part of the solution".into()),
+        (Some(output), _) => match output.status() {
+            PartStatus::Panicked | PartStatus::Failed => {
+                Outcome::Failed(output.error.clone().unwrap_or_default())
+            }
+            PartStatus::NotImplemented | PartStatus::Missing => {
+                Outcome::Skipped("not implemented".into())
+            }
+            PartStatus::Solved => match &expected {
+                _ if run.expected.is_none() => Outcome::Passed,
+                None => Outcome::Skipped("no known answer".into()),
+                Some(expected) => {
+                    if run.wrong_answers().any(|(p, _)| p.part == part) {
+                        let actual = output.answer.as_deref().unwrap_or_default();
+                        Outcome::Failed(format!("expected {expected}, got {actual}"))
+                    } else {
+                        Outcome::Passed
+                    }
+                }
+            },
+        },
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = run
+        .timing
+        .as_ref()
+        .and_then(|x| x.part(part))
+        .map(|x| Duration::from_nanos(x.nanos as u64));
+
+    TestCase {
+        day: run.day,
+        part,
+        outcome,
+        expected,
+        actual: output.and_then(|x| x.answer.clone()),
+        duration,
+    }
+}
+
+/// Writes a report of the test cases to its target path.
+pub fn write(target: &ReportTarget, cases: &[TestCase]) -> Result<(), io::Error> {
+    let report = match target.format {
+        ReportFormat::Junit => to_junit(cases),
+        ReportFormat::Tap => to_tap(cases),
+    };
+
+    if let Some(parent) = target.path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(&target.path, report)
+}
+
+fn count(cases: &[&TestCase], f: impl Fn(&Outcome) -> bool) -> usize {
+    cases.iter().filter(|x| f(&x.outcome)).count()
+}
+
+fn total_seconds(cases: &[&TestCase]) -> f64 {
+    cases
+        .iter()
+        .filter_map(|x| x.duration)
+        .sum::<Duration>()
+        .as_secs_f64()
+}
+
+/// Renders a JUnit XML report with one test suite per day.
+pub fn to_junit(cases: &[TestCase]) -> String {
+    let all: Vec<&TestCase> = cases.iter().collect();
+    let is_failed = |x: &Outcome| matches!(x, Outcome::Failed(_));
+    let is_skipped = |x: &Outcome| matches!(x, Outcome::Skipped(_));
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"advent_of_code\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+        all.len(),
+        count(&all, is_failed),
+        count(&all, is_skipped),
+        total_seconds(&all)
+    );
+
+    let mut days: Vec<Day> = cases.iter().map(|x| x.day).collect();
+    days.dedup();
+
+    for day in days {
+        let suite: Vec<&TestCase> = cases.iter().filter(|x| x.day == day).collect();
+
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"Day {day}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+            suite.len(),
+            count(&suite, is_failed),
+            count(&suite, is_skipped),
+            total_seconds(&suite)
+        );
+
+        for case in suite {
+            let time = case.duration.unwrap_or_default().as_secs_f64();
+            let _ = write!(
+                xml,
+                "    <testcase classname=\"day{day}\" name=\"{}\" time=\"{time:.6}\"",
+                escape_xml(&case.name())
+            );
+
+            match &case.outcome {
+                Outcome::Passed => xml.push_str(" />\n"),
+                Outcome::Skipped(message) => {
+                    let _ = writeln!(
+                        xml,
+                        ">\n      <skipped message=\"{}\" />\n    </testcase>",
+                        escape_xml(message)
+                    );
+                }
+                Outcome::Failed(message) => {
+                    let _ = writeln!(
+                        xml,
+                        ">\n      <failure message=\"{}\">expected: {}\nactual: {}</failure>\n    </testcase>",
+                        escape_xml(message),
+                        escape_xml(case.expected.as_deref().unwrap_or("-")),
+                        escape_xml(case.actual.as_deref().unwrap_or("-"))
+                    );
+                }
+            }
+        }
+
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Renders a TAP version 13 report, with details of failures in YAML blocks.
+pub fn to_tap(cases: &[TestCase]) -> String {
+    let mut tap = format!("TAP version 13\n1..{}\n", cases.len());
+
+    for (i, case) in cases.iter().enumerate() {
+        let number = i + 1;
+        let name = case.name();
+
+        match &case.outcome {
+            Outcome::Passed => {
+                let _ = writeln!(tap, "ok {number} - {name}");
+            }
+            Outcome::Skipped(reason) => {
+                let _ = writeln!(tap, "ok {number} - {name} # SKIP {reason}");
+            }
+            Outcome::Failed(message) => {
+                let _ = writeln!(tap, "not ok {number} - {name}");
+                tap.push_str("  ---\n");
+                let _ = writeln!(tap, "  message: {}", quote_yaml(message));
+                if let Some(expected) = &case.expected {
+                    let _ = writeln!(tap, "  expected: {}", quote_yaml(expected));
+                }
+                if let Some(actual) = &case.actual {
+                    let _ = writeln!(tap, "  actual: {}", quote_yaml(actual));
+                }
+                if let Some(duration) = case.duration {
+                    let _ = writeln!(tap, "  duration_ms: {:.6}", duration.as_secs_f64() * 1000.0);
+                }
+                tap.push_str("  ...\n");
+            }
+        }
+    }
+
+    tap
+}
+
+fn quote_yaml(s: &str) -> String {
+    format!(
+        "\"{}\"",
+        s.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        collect_test_cases, to_junit, to_tap, Outcome, ReportFormat, ReportTarget, TestCase,
+    };
+    use crate::{
+        day,
+        template::run_multi::{child_commands::PartOutput, DayRun, DayStatus},
+        template::timings::{PartTiming, Timing},
+    };
+
+    fn cases() -> Vec<TestCase> {
+        vec![
+            TestCase {
+                day: day!(1),
+                part: 1,
+                outcome: Outcome::Passed,
+                expected: Some("42".into()),
+                actual: Some("42".into()),
+                duration: Some(Duration::from_micros(1500)),
+            },
+            TestCase {
+                day: day!(1),
+                part: 2,
+                outcome: Outcome::Failed("expected \"<1>\", got 2".into()),
+                expected: Some("<1>".into()),
+                actual: Some("2".into()),
+                duration: None,
+            },
+            TestCase {
+                day: day!(2),
+                part: 1,
+                outcome: Outcome::Skipped("not implemented".into()),
+                expected: None,
+                actual: None,
+                duration: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn takes_durations_from_timings() {
+        let part = |part: u8| PartOutput {
+            part,
+            answer: Some("1".into()),
+            error: None,
+            duration: Some("999.0s".into()),
+        };
+        let run = DayRun {
+            day: day!(1),
+            status: DayStatus::Ok,
+            parts: vec![part(1), part(2)],
+            timing: Some(Timing {
+                day: day!(1),
+                part_1: Some(PartTiming {
+                    nanos: 1_500_000.0,
+                    samples: Some(10),
+                    std_dev: None,
+                }),
+                part_2: None,
+                total_nanos: 1_500_000.0,
+                status: Default::default(),
+            }),
+            expected: None,
+            error: None,
+        };
+
+        let cases = collect_test_cases(&[run]);
+        assert_eq!(cases[0].duration, Some(Duration::from_micros(1500)));
+        assert_eq!(cases[1].duration, None);
+    }
+
+    #[test]
+    fn parses_report_targets() {
+        assert_eq!(
+            "junit=target/report.xml".parse(),
+            Ok(ReportTarget {
+                format: ReportFormat::Junit,
+                path: "target/report.xml".into()
+            })
+        );
+        assert!("tap=".parse::<ReportTarget>().is_err());
+        assert!("html=report.html".parse::<ReportTarget>().is_err());
+        assert!("junit".parse::<ReportTarget>().is_err());
+    }
+
+    #[test]
+    fn renders_junit() {
+        assert_eq!(
+            to_junit(&cases()),
+            [
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+                "<testsuites name=\"advent_of_code\" tests=\"3\" failures=\"1\" skipped=\"1\" time=\"0.001500\">",
+                "  <testsuite name=\"Day 01\" tests=\"2\" failures=\"1\" skipped=\"0\" time=\"0.001500\">",
+                "    <testcase classname=\"day01\" name=\"Day 01 Part 1\" time=\"0.001500\" />",
+                "    <testcase classname=\"day01\" name=\"Day 01 Part 2\" time=\"0.000000\">",
+                "      <failure message=\"expected &quot;&lt;1&gt;&quot;, got 2\">expected: &lt;1&gt;",
+                "actual: 2</failure>",
+                "    </testcase>",
+                "  </testsuite>",
+                "  <testsuite name=\"Day 02\" tests=\"1\" failures=\"0\" skipped=\"1\" time=\"0.000000\">",
+                "    <testcase classname=\"day02\" name=\"Day 02 Part 1\" time=\"0.000000\">",
+                "      <skipped message=\"not implemented\" />",
+                "    </testcase>",
+                "  </testsuite>",
+                "</testsuites>",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn renders_tap() {
+        assert_eq!(
+            to_tap(&cases()),
+            [
+                "TAP version 13",
+                "1..3",
+                "ok 1 - Day 01 Part 1",
+                "not ok 2 - Day 01 Part 2",
+                "  ---",
+                "  message: \"expected \\\"<1>\\\", got 2\"",
+                "  expected: \"<1>\"",
+                "  actual: \"2\"",
+                "  ...",
+                "ok 3 - Day 02 Part 1 # SKIP not implemented",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
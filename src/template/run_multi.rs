@@ -1,14 +1,117 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, fmt::Display, time::Duration};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{config, output, Day};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    answers::{self, Answers},
+    profile,
+    timings::{PartStatus, Timing, Timings},
 };
+use child_commands::{BuildProfile, PartOutput, SolutionOutput};
+use output::OutputFormat;
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// The outcome of running the solution of a single day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DayStatus {
+    Ok,
+    /// A part returned `None` or is not part of the solution.
+    NotImplemented,
+    NotScaffolded,
+    BuildFailed,
+    /// A part returned an error, or the solution bin exited with a non-zero status.
+    Failed,
+    Panicked,
+    TimedOut,
+    /// An answer differs from the known answer, only checked with `--check`.
+    WrongAnswer,
+}
+
+impl DayStatus {
+    pub fn is_failure(self) -> bool {
+        matches!(
+            self,
+            DayStatus::BuildFailed
+                | DayStatus::Failed
+                | DayStatus::Panicked
+                | DayStatus::TimedOut
+                | DayStatus::WrongAnswer
+        )
+    }
+}
+
+impl Display for DayStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            DayStatus::Ok => "ok",
+            DayStatus::NotImplemented => "not implemented",
+            DayStatus::NotScaffolded => "not scaffolded",
+            DayStatus::BuildFailed => "build failed",
+            DayStatus::Failed => "failed",
+            DayStatus::Panicked => "panicked",
+            DayStatus::TimedOut => "timed out",
+            DayStatus::WrongAnswer => "wrong answer",
+        };
+        f.write_str(label)
+    }
+}
+
+/// The result of running the solution of a single day.
+pub struct DayRun {
+    pub day: Day,
+    pub status: DayStatus,
+    pub parts: Vec<PartOutput>,
+    /// Timings of the parts, if the solution printed any results.
+    pub timing: Option<Timing>,
+    /// Known answers for the puzzle input, only read with `--check`.
+    pub expected: Option<Answers>,
+    /// The last line of the error output, if the solution bin exited with a non-zero status.
+    pub error: Option<String>,
+}
+
+impl DayRun {
+    /// Sum of the durations of all parts, if any were reported.
+    pub fn total_duration(&self) -> Option<Duration> {
+        let timing = self.timing.as_ref()?;
+        if timing.part_1.is_none() && timing.part_2.is_none() {
+            return None;
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Some(Duration::from_nanos(timing.total_nanos as u64))
+    }
+
+    /// Parts whose answer differs from the known answer.
+    pub fn wrong_answers(&self) -> impl Iterator<Item = (&PartOutput, &str)> {
+        self.parts.iter().filter_map(|part| {
+            let expected = self.expected.as_ref()?.get(part.part)?;
+            let answer = part.answer.as_deref().map(answers::normalise);
+            (answer.as_deref() != Some(expected)).then_some((part, expected))
+        })
+    }
+
+    /// Marks a run that did not fail otherwise as a wrong answer if an answer differs from the known answer.
+    fn check_answers(&mut self) {
+        if !self.status.is_failure() && self.wrong_answers().next().is_some() {
+            self.status = DayStatus::WrongAnswer;
+        }
+    }
+}
+
+/// Run the solutions of multiple days, printing their output followed by a summary table.
+/// The `timeout` applies to each day, including the time needed to build its solution.
+/// If `is_checked` is set, answers are compared with the known answers stored next to the puzzle inputs.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    profile: BuildProfile,
+    is_timed: bool,
+    is_checked: bool,
+    timeout: Option<Duration>,
+) -> Vec<DayRun> {
+    let mut runs: Vec<DayRun> = Vec::with_capacity(days_to_run.len());
+    let reporter = output::reporter();
+    // in the quiet format, only the summary is printed.
+    let is_quiet = output::format() == OutputFormat::Quiet;
 
     let mut need_space = false;
 
@@ -16,116 +119,458 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     all_days()
         .filter(|day| days_to_run.contains(day))
         .for_each(|day| {
-            if need_space {
+            if need_space && !is_quiet {
                 println!();
             }
             need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            reporter.heading(&format!("Day {day}"));
+            reporter.heading("------");
+
+            // skip command invocation for days that have not been scaffolded yet.
+            if !config::bin_path(day).exists() {
+                reporter.info("Not scaffolded.");
+                runs.push(DayRun {
+                    day,
+                    status: DayStatus::NotScaffolded,
+                    parts: vec![],
+                    timing: None,
+                    expected: None,
+                    error: None,
+                });
+                return;
+            }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output = child_commands::run_solution(day, is_timed, profile, timeout)
+                .unwrap_or_else(|e| SolutionOutput {
+                    stdout: vec![],
+                    stderr: vec![e.to_string()],
+                    success: false,
+                    timed_out: false,
+                });
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+            if output.timed_out {
+                reporter.info("Timed out.");
             }
+
+            let parts = child_commands::parse_part_outputs(&output.stdout);
+
+            let expected = is_checked.then(|| {
+                Answers::read_for(&profile::get_input_path(day)).unwrap_or_else(|e| {
+                    eprintln!("Could not parse known answers: {e}");
+                    Answers::default()
+                })
+            });
+
+            let mut run = DayRun {
+                day,
+                status: get_day_status(&output, &parts),
+                parts,
+                timing: (!output.stdout.is_empty())
+                    .then(|| child_commands::parse_exec_time(&output.stdout, day)),
+                expected,
+                error: (!output.success)
+                    .then(|| output.stderr.iter().rev().find(|l| !l.trim().is_empty()))
+                    .flatten()
+                    .cloned(),
+            };
+
+            run.check_answers();
+            runs.push(run);
         });
 
+    print_failures(&runs);
+    print_summary(&runs);
+
     if is_timed {
-        let timings = Timings { data: timings };
+        let timings = Timings {
+            data: runs.iter().filter_map(|run| run.timing.clone()).collect(),
+        };
         let total_millis = timings.total_millis();
         println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            "\n{} {}",
+            reporter.strong("Total (Run):"),
+            reporter.subtle(&format!("{total_millis:.2}ms"))
         );
-        Some(timings)
+    }
+
+    runs
+}
+
+fn get_day_status(output: &SolutionOutput, parts: &[PartOutput]) -> DayStatus {
+    let statuses: Vec<PartStatus> = parts.iter().map(PartOutput::status).collect();
+
+    if output.timed_out {
+        DayStatus::TimedOut
+    } else if statuses.contains(&PartStatus::Panicked) {
+        DayStatus::Panicked
+    } else if !output.success && is_build_failure(&output.stderr) {
+        DayStatus::BuildFailed
+    } else if !output.success || statuses.contains(&PartStatus::Failed) {
+        DayStatus::Failed
+    } else if statuses.is_empty() || statuses.contains(&PartStatus::NotImplemented) {
+        DayStatus::NotImplemented
     } else {
-        None
+        DayStatus::Ok
     }
 }
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
+fn is_build_failure(stderr: &[String]) -> bool {
+    stderr
+        .iter()
+        .any(|line| line.starts_with("error: could not compile"))
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+fn print_failures(runs: &[DayRun]) {
+    let failures: Vec<(Day, &PartOutput)> = runs
+        .iter()
+        .flat_map(|run| run.parts.iter().map(move |part| (run.day, part)))
+        .filter(|(_, part)| part.error.is_some())
+        .collect();
+
+    let wrong_answers: Vec<(Day, &PartOutput, &str)> = runs
+        .iter()
+        .flat_map(|run| {
+            run.wrong_answers()
+                .map(move |(part, expected)| (run.day, part, expected))
+        })
+        .filter(|(_, part, _)| part.error.is_none())
+        .collect();
+
+    if !failures.is_empty() || !wrong_answers.is_empty() {
+        println!("\n{}", output::reporter().strong("Failed:"));
+        for (day, part) in &failures {
+            let error = part.error.as_deref().unwrap_or_default();
+            println!("Day {day}, Part {}: {error}", part.part);
+        }
+        for (day, part, expected) in &wrong_answers {
+            let answer = part.answer.as_deref().unwrap_or("✖");
+            println!(
+                "Day {day}, Part {}: {answer}, expected {expected}",
+                part.part
+            );
+        }
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Print a compact table with the answers, status and duration of every day.
+fn print_summary(runs: &[DayRun]) {
+    let show_time = runs.iter().any(|run| run.total_duration().is_some());
+
+    let mut rows: Vec<Vec<String>> = vec![];
+
+    let mut header = vec![
+        "Day".into(),
+        "Part 1".into(),
+        "Part 2".into(),
+        "Status".into(),
+    ];
+    if show_time {
+        header.push("Time".into());
+    }
+
+    for run in runs {
+        let mut row = vec![
+            run.day.to_string(),
+            format_answer(run, 1),
+            format_answer(run, 2),
+            run.status.to_string(),
+        ];
+        if show_time {
+            row.push(
+                run.total_duration()
+                    .map(|d| format!("{d:.1?}"))
+                    .unwrap_or_default(),
+            );
+        }
+        rows.push(row);
+    }
+
+    println!("\n{}", output::reporter().strong("Summary"));
+    output::print_table(&header, &rows);
+}
+
+fn format_answer(run: &DayRun, part: u8) -> String {
+    let Some(output) = run.parts.iter().find(|p| p.part == part) else {
+        return "-".into();
+    };
+
+    match &output.answer {
+        Some(answer) if answer.contains('\n') => "▼".into(),
+        Some(answer) => answer.clone(),
+        None => "✖".into(),
+    }
 }
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{
+        input::InputSource,
+        output::{self, OutputFormat},
+        solution_args::SolutionArgs,
+        timings::{parse_nanos, PartStatus, PartTiming},
+        Day, Error,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        io::{self, BufRead, BufReader},
+        process::{Child, Command, ExitStatus, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// Run the solution bin for a given day, forwarding its output while capturing it.
+    /// In the quiet format, the output is only captured.
+    /// If it does not finish within the `timeout`, it is killed and reported as timed out.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        profile: BuildProfile,
+        timeout: Option<Duration>,
+    ) -> Result<SolutionOutput, Error> {
+        let is_quiet = output::format() == OutputFormat::Quiet;
+
+        // mirror `--time` flag and output format to child invocations, answers are parsed from result lines.
+        let child_args = SolutionArgs {
+            time: is_timed,
+            format: Some(if is_quiet {
+                OutputFormat::Plain
+            } else {
+                output::format()
+            }),
+            ..Default::default()
+        };
+
+        let mut command = Command::new("cargo");
+        command
+            .args(build_args(day, profile, &child_args.to_args()))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        // a separate process group allows killing the solution bin together with cargo.
+        #[cfg(unix)]
+        if timeout.is_some() {
+            use std::os::unix::process::CommandExt;
+            command.process_group(0);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let to_error = |e| Error::io(format!("run the solution of day {day}"), e);
+
+        let mut cmd = command.spawn().map_err(to_error)?;
 
-        if is_release {
-            args.push("--release");
+        let (Some(stdout), Some(stderr)) = (cmd.stdout.take(), cmd.stderr.take()) else {
+            return Err(to_error(io::ErrorKind::BrokenPipe.into()));
+        };
+        let (stdout, stderr) = (BufReader::new(stdout), BufReader::new(stderr));
+
+        let stdout = thread::spawn(move || {
+            forward_lines(stdout, |line| {
+                if !is_quiet {
+                    println!("{line}")
+                }
+            })
+        });
+        let stderr = thread::spawn(move || {
+            forward_lines(stderr, |line| {
+                if !is_quiet {
+                    eprintln!("{line}")
+                }
+            })
+        });
+
+        let status = wait_with_timeout(&mut cmd, timeout).map_err(to_error)?;
+
+        Ok(SolutionOutput {
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+            success: status.is_some_and(|s| s.success()),
+            timed_out: status.is_none(),
+        })
+    }
+
+    fn forward_lines(reader: impl BufRead, print: impl Fn(&str)) -> Vec<String> {
+        reader
+            .lines()
+            .map_while(Result::ok)
+            .inspect(|line| print(line))
+            .collect()
+    }
+
+    /// Wait for a child to exit, returning `None` if it was killed after the timeout.
+    fn wait_with_timeout(
+        cmd: &mut Child,
+        timeout: Option<Duration>,
+    ) -> io::Result<Option<ExitStatus>> {
+        let Some(timeout) = timeout else {
+            return Ok(Some(cmd.wait()?));
+        };
+
+        let start = Instant::now();
+
+        loop {
+            if let Some(status) = cmd.try_wait()? {
+                return Ok(Some(status));
+            }
+
+            if start.elapsed() >= timeout {
+                kill_process_tree(cmd);
+                cmd.wait()?;
+                return Ok(None);
+            }
+
+            thread::sleep(Duration::from_millis(10));
         }
+    }
+
+    /// Kill a child and its descendants, as killing `cargo run` does not stop the solution bin it started.
+    fn kill_process_tree(cmd: &mut Child) {
+        #[cfg(unix)]
+        let _ = Command::new("kill")
+            .args(["-KILL", "--", &format!("-{}", cmd.id())])
+            .status();
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        #[cfg(windows)]
+        let _ = Command::new("taskkill")
+            .args(["/F", "/T", "/PID", &cmd.id().to_string()])
+            .status();
+
+        let _ = cmd.kill();
+    }
+
+    /// The captured output of a solution bin.
+    pub struct SolutionOutput {
+        pub stdout: Vec<String>,
+        pub stderr: Vec<String>,
+        pub success: bool,
+        pub timed_out: bool,
+    }
+
+    impl SolutionOutput {
+        /// Extracts the panic location and message from the stderr of a solution bin that crashed.
+        pub fn panic_message(&self) -> String {
+            let stderr = &self.stderr;
+            let Some(idx) = stderr.iter().position(|l| l.contains("panicked at")) else {
+                return "exited with a non-zero status".into();
+            };
+
+            let location = stderr[idx]
+                .split("panicked at ")
+                .last()
+                .unwrap_or_default()
+                .trim_end_matches(':');
+
+            match stderr.get(idx + 1) {
+                Some(message) if !message.starts_with("note:") => {
+                    format!("panicked at {location}: {message}")
+                }
+                _ => format!("panicked at {location}"),
+            }
         }
+    }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+    /// Run the solution bin for a given day against a specific input, capturing its output instead of forwarding it.
+    pub fn run_solution_with_input(
+        day: Day,
+        is_release: bool,
+        input: &InputSource,
+    ) -> Result<SolutionOutput, Error> {
+        run_solution_with_env(day, is_release, input, &[])
+    }
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+    /// Like [`run_solution_with_input`], setting additional environment variables for the solution bin.
+    pub fn run_solution_with_env(
+        day: Day,
+        is_release: bool,
+        input: &InputSource,
+        envs: &[(&str, String)],
+    ) -> Result<SolutionOutput, Error> {
+        let child_args = SolutionArgs {
+            input: input.clone(),
+            format: Some(OutputFormat::Plain),
+            ..Default::default()
+        };
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let output = Command::new("cargo")
+            .args(build_args(
+                day,
+                BuildProfile::from_flags(is_release, false),
+                &child_args.to_args(),
+            ))
+            .envs(envs.iter().map(|(key, value)| (key, value)))
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| Error::io(format!("run the solution of day {day}"), e))?;
 
-        let mut output = vec![];
+        let lines = |bytes: &[u8]| {
+            String::from_utf8_lossy(bytes)
+                .lines()
+                .map(String::from)
+                .collect()
+        };
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        Ok(SolutionOutput {
+            stdout: lines(&output.stdout),
+            stderr: lines(&output.stderr),
+            success: output.status.success(),
+            timed_out: false,
+        })
+    }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+    /// The cargo profile solution bins are built with.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum BuildProfile {
+        Debug,
+        Release,
+        /// Optimised like `Release`, but panicking on integer overflow and failed debug assertions.
+        /// See `[profile.checked]` in `Cargo.toml`.
+        Checked,
+    }
+
+    impl BuildProfile {
+        /// Selects the profile of the `--release` and `--checked` flags, the latter taking precedence.
+        pub fn from_flags(is_release: bool, is_checked: bool) -> Self {
+            match (is_release, is_checked) {
+                (_, true) => Self::Checked,
+                (true, false) => Self::Release,
+                (false, false) => Self::Debug,
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        /// Returns the arguments of `cargo run` selecting the profile.
+        pub fn to_args(self) -> Vec<String> {
+            match self {
+                Self::Debug => vec![],
+                Self::Release => vec!["--release".into()],
+                Self::Checked => vec!["--profile".into(), "checked".into()],
+            }
+        }
 
-        Ok(output)
+        /// Returns the name of the cargo profile, e.g. `release`.
+        pub fn name(self) -> &'static str {
+            match self {
+                Self::Debug => "dev",
+                Self::Release => "release",
+                Self::Checked => "checked",
+            }
+        }
+    }
+
+    fn build_args(day: Day, profile: BuildProfile, child_args: &[String]) -> Vec<String> {
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            day.to_string(),
+        ];
+
+        args.extend(profile.to_args());
+
+        if !child_args.is_empty() {
+            args.push("--".into());
+            args.extend_from_slice(child_args);
+        }
+
+        args
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -134,8 +579,18 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            status: Default::default(),
         };
 
+        let parts = parse_part_outputs(output);
+
+        for part in &parts {
+            let index = usize::from(part.part).checked_sub(1);
+            if let Some(status) = index.and_then(|i| timings.status.get_mut(i)) {
+                *status = part.status();
+            }
+        }
+
         output
             .iter()
             .filter_map(|l| {
@@ -143,50 +598,191 @@ pub mod child_commands {
                     return None;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
+                let Some(timing) = parse_part_timing(l) else {
                     eprintln!("Could not parse timings from line: {l}");
                     return None;
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing)| {
                 if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
+                    timings.part_1 = Some(timing);
                 } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                    timings.part_2 = Some(timing);
                 }
 
-                timings.total_nanos += nanos;
+                timings.total_nanos += timing.nanos;
             });
 
+        // without `--time`, solutions print the duration of a single run instead.
+        for part in &parts {
+            let timing = match part.part {
+                1 => &mut timings.part_1,
+                2 => &mut timings.part_2,
+                _ => continue,
+            };
+            if timing.is_some() {
+                continue;
+            }
+            if let Some(nanos) = part.duration.as_deref().and_then(parse_nanos) {
+                *timing = Some(PartTiming {
+                    nanos,
+                    samples: None,
+                    std_dev: None,
+                });
+                timings.total_nanos += nanos;
+            }
+        }
+
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// The result a solution bin printed for a single part.
+    #[derive(Debug, PartialEq)]
+    pub struct PartOutput {
+        pub part: u8,
+        pub answer: Option<String>,
+        pub error: Option<String>,
+        pub duration: Option<String>,
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
+    impl PartOutput {
+        pub fn status(&self) -> PartStatus {
+            match (&self.answer, &self.error) {
+                (Some(_), _) => PartStatus::Solved,
+                (None, Some(error)) if error.starts_with("panicked") => PartStatus::Panicked,
+                (None, Some(_)) => PartStatus::Failed,
+                (None, None) => PartStatus::NotImplemented,
+            }
+        }
+    }
+
+    /// Parse the result lines of all parts from the output of a solution bin.
+    pub fn parse_part_outputs(output: &[String]) -> Vec<PartOutput> {
+        let mut parts: Vec<PartOutput> = vec![];
+        let mut multiline: Option<Vec<String>> = None;
+
+        for line in output {
+            // intermediate results are overwritten with a carriage return, only keep the final one.
+            let line = strip_ansi(line.rsplit('\r').next().unwrap_or_default());
+
+            let Some((part, rest)) = parse_part_prefix(&line) else {
+                if let Some(lines) = multiline.as_mut() {
+                    lines.push(line);
+                }
+                continue;
+            };
+
+            if let (Some(lines), Some(last)) = (multiline.take(), parts.last_mut()) {
+                last.answer = Some(lines.join("\n"));
+            }
+
+            // errors are printed in place of the answer, without a duration.
+            if let Some(error) = rest.strip_prefix("✖ ") {
+                parts.push(PartOutput {
+                    part,
+                    answer: None,
+                    error: Some(error.trim().to_string()),
+                    duration: None,
+                });
+                continue;
+            }
+
+            let (answer, duration) = split_duration(rest);
+
+            let answer = match answer {
+                "✖" => None,
+                "▼" => {
+                    multiline = Some(vec![]);
+                    None
+                }
+                x => Some(x.to_string()),
+            };
+
+            parts.push(PartOutput {
+                part,
+                answer,
+                error: None,
+                duration: duration.map(String::from),
+            });
+        }
+
+        if let (Some(lines), Some(last)) = (multiline, parts.last_mut()) {
+            // the multiline answer is followed by an empty line if it was printed last.
+            let answer = lines.join("\n");
+            last.answer = Some(answer.trim_end_matches('\n').to_string());
+        }
+
+        parts
+    }
+
+    fn parse_part_prefix(line: &str) -> Option<(u8, &str)> {
+        let rest = line.strip_prefix("Part ")?;
+        let (part, rest) = rest.split_once(':')?;
+        Some((part.parse().ok()?, rest.trim()))
+    }
+
+    /// Split a result like `42 (1.2ms @ 10 samples)` into the answer and its duration.
+    fn split_duration(s: &str) -> (&str, Option<&str>) {
+        if !s.ends_with(')') {
+            return (s, None);
+        }
+
+        match s.rfind(" (").or_else(|| s.starts_with('(').then_some(0)) {
+            Some(idx) => {
+                let duration = s[idx..]
+                    .trim()
+                    .trim_start_matches('(')
+                    .trim_end_matches(')');
+                // only keep the mean of a benchmark like `1.2ms ± 0.1ms @ 10 samples`.
+                let duration = duration.split(['@', '±']).next().unwrap_or_default().trim();
+                (s[..idx].trim(), Some(duration))
+            }
+            None => (s, None),
+        }
+    }
+
+    fn strip_ansi(s: &str) -> String {
+        let mut result = String::with_capacity(s.len());
+        let mut chars = s.chars();
+
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                // skip until the end of the escape sequence, e.g. `\x1b[1m`.
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            } else {
+                result.push(c);
+            }
+        }
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        result
+    }
+
+    /// Parse a benchmark like `(74.13ns ± 1.2ns @ 100000 samples)` at the end of a result line.
+    /// The standard deviation is missing in output of older versions of the template.
+    fn parse_part_timing(line: &str) -> Option<PartTiming> {
+        let (rest, samples) = line
+            .trim_end()
+            .strip_suffix(" samples)")?
+            .rsplit_once(" @ ")?;
+        let duration = &rest[rest.rfind('(')? + 1..];
+
+        let (mean, std_dev) = match duration.split_once(" ± ") {
+            Some((mean, std_dev)) => (mean, Some(parse_nanos(std_dev)?)),
+            None => (duration, None),
+        };
 
-        Some((str_timing, parsed_timing))
+        Some(PartTiming {
+            nanos: parse_nanos(mean)?,
+            samples: Some(samples.parse().ok()?),
+            std_dev,
+        })
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,7 +801,8 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, parse_part_outputs, PartOutput};
+        use crate::template::timings::PartStatus;
 
         use crate::day;
 
@@ -220,8 +817,25 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.part_1.unwrap().nanos, 74.13_f64);
+            assert_eq!(res.part_1.unwrap().samples, Some(100000));
+            assert_approx_eq!(res.part_2.unwrap().nanos, 74130000_f64);
+        }
+
+        #[test]
+        fn parses_standard_deviations() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 42 (1.5ms ± 20.0µs @ 667 samples)".into(),
+                    "Part 2: 17 (2.0ms @ 10 samples)".into(),
+                ],
+                day!(1),
+            );
+            let part_1 = res.part_1.unwrap();
+            assert_approx_eq!(part_1.nanos, 1_500_000_f64);
+            assert_approx_eq!(part_1.std_dev.unwrap(), 20_000_f64);
+            assert_eq!(part_1.samples, Some(667));
+            assert_eq!(res.part_2.unwrap().std_dev, None);
         }
 
         #[test]
@@ -235,8 +849,24 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.part_1.unwrap().nanos, 2e9_f64);
+            assert_approx_eq!(res.part_2.unwrap().nanos, 1e8_f64);
+        }
+
+        #[test]
+        fn parses_durations_of_single_runs() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 42 (1.5ms)".into(),
+                    "Part 2: 17 (2.0ms @ 10 samples)".into(),
+                ],
+                day!(1),
+            );
+            let part_1 = res.part_1.unwrap();
+            assert_approx_eq!(part_1.nanos, 1_500_000_f64);
+            assert_eq!(part_1.samples, None);
+            assert_eq!(res.part_2.unwrap().samples, Some(10));
+            assert_approx_eq!(res.total_nanos, 3_500_000_f64);
         }
 
         #[test]
@@ -252,6 +882,247 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.status, [PartStatus::NotImplemented; 2]);
+        }
+
+        #[test]
+        fn parses_panicked_parts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: ✖ panicked at src/bin/01.rs:14:39: index out of bounds".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.is_none(), true);
+            assert_approx_eq!(res.part_2.unwrap().nanos, 74130000_f64);
+            assert_eq!(res.status, [PartStatus::Panicked, PartStatus::Solved]);
+        }
+
+        #[test]
+        fn parses_part_outputs() {
+            let res = parse_part_outputs(&[
+                "Input: \"foo.txt\"".into(),
+                "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.2ms)".into(),
+                "Part 2: (a) (b) (74.13ns @ 100000 samples)".into(),
+            ]);
+            assert_eq!(
+                res,
+                vec![
+                    PartOutput {
+                        part: 1,
+                        answer: Some("42".into()),
+                        error: None,
+                        duration: Some("1.2ms".into()),
+                    },
+                    PartOutput {
+                        part: 2,
+                        answer: Some("(a) (b)".into()),
+                        error: None,
+                        duration: Some("74.13ns".into()),
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn parses_missing_and_multiline_part_outputs() {
+            let res = parse_part_outputs(&[
+                "Part 1: ▼\rPart 1: ▼  (10.0µs)".into(),
+                "#..#".into(),
+                ".##.".into(),
+                "Part 2: ✖\rPart 2: ✖             ".into(),
+            ]);
+            assert_eq!(res[0].answer, Some("#..#\n.##.".into()));
+            assert_eq!(res[0].duration, Some("10.0µs".into()));
+            assert_eq!(res[1].answer, None);
+            assert_eq!(res[1].error, None);
+            assert_eq!(res[1].duration, None);
+        }
+
+        #[test]
+        fn parses_part_errors() {
+            let res = parse_part_outputs(&[
+                "Part 1: ✖\rPart 1: ✖ expected two numbers (found `1`)".into(),
+            ]);
+            assert_eq!(res[0].answer, None);
+            assert_eq!(
+                res[0].error,
+                Some("expected two numbers (found `1`)".into())
+            );
+            assert_eq!(res[0].duration, None);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        child_commands::{PartOutput, SolutionOutput},
+        format_answer, get_day_status, is_build_failure, DayRun, DayStatus,
+    };
+    use crate::{day, template::answers::Answers};
+
+    fn solved(part: u8, answer: &str) -> PartOutput {
+        PartOutput {
+            part,
+            answer: Some(answer.into()),
+            error: None,
+            duration: None,
+        }
+    }
+
+    fn unsolved(part: u8, error: Option<&str>) -> PartOutput {
+        PartOutput {
+            part,
+            answer: None,
+            error: error.map(String::from),
+            duration: None,
+        }
+    }
+
+    fn output(success: bool, stderr: &[&str]) -> SolutionOutput {
+        SolutionOutput {
+            stdout: vec![],
+            stderr: stderr.iter().map(|line| line.to_string()).collect(),
+            success,
+            timed_out: false,
+        }
+    }
+
+    fn run(parts: Vec<PartOutput>, expected: Option<Answers>) -> DayRun {
+        DayRun {
+            day: day!(1),
+            status: DayStatus::Ok,
+            parts,
+            timing: None,
+            expected,
+            error: None,
         }
     }
+
+    #[test]
+    fn detects_build_failures() {
+        assert!(is_build_failure(&[
+            "error[E0425]: cannot find value `x` in this scope".into(),
+            "error: could not compile `advent_of_code` (bin \"01\") due to 1 previous error".into(),
+        ]));
+        assert!(!is_build_failure(&[
+            "thread 'main' panicked at src/bin/01.rs:4:5:".into(),
+            "   Compiling advent_of_code v0.11.0".into(),
+        ]));
+        assert!(!is_build_failure(&[]));
+    }
+
+    #[test]
+    fn derives_day_statuses() {
+        let parts = vec![solved(1, "1"), solved(2, "2")];
+        assert_eq!(get_day_status(&output(true, &[]), &parts), DayStatus::Ok);
+
+        let timed_out = SolutionOutput {
+            timed_out: true,
+            ..output(false, &[])
+        };
+        assert_eq!(get_day_status(&timed_out, &parts), DayStatus::TimedOut);
+
+        let panicked = vec![
+            solved(1, "1"),
+            unsolved(2, Some("panicked at src/bin/01.rs:4:5")),
+        ];
+        assert_eq!(
+            get_day_status(&output(false, &[]), &panicked),
+            DayStatus::Panicked
+        );
+
+        let build_failure = output(false, &["error: could not compile `advent_of_code`"]);
+        assert_eq!(get_day_status(&build_failure, &[]), DayStatus::BuildFailed);
+
+        let failed = vec![solved(1, "1"), unsolved(2, Some("expected a number"))];
+        assert_eq!(
+            get_day_status(&output(true, &[]), &failed),
+            DayStatus::Failed
+        );
+        assert_eq!(
+            get_day_status(&output(false, &["error"]), &parts),
+            DayStatus::Failed
+        );
+
+        let not_implemented = vec![solved(1, "1"), unsolved(2, None)];
+        assert_eq!(
+            get_day_status(&output(true, &[]), &not_implemented),
+            DayStatus::NotImplemented
+        );
+        assert_eq!(
+            get_day_status(&output(true, &[]), &[]),
+            DayStatus::NotImplemented
+        );
+    }
+
+    #[test]
+    fn marks_wrong_answers() {
+        let expected = || {
+            Some(Answers {
+                part_1: Some("1".into()),
+                part_2: Some("2".into()),
+            })
+        };
+
+        let mut correct = run(vec![solved(1, "1"), solved(2, "2")], expected());
+        correct.check_answers();
+        assert_eq!(correct.status, DayStatus::Ok);
+
+        let mut wrong = run(vec![solved(1, "1"), solved(2, "3")], expected());
+        wrong.check_answers();
+        assert_eq!(wrong.status, DayStatus::WrongAnswer);
+        assert_eq!(wrong.wrong_answers().count(), 1);
+
+        // answers are not checked without known answers, and failures are not overridden.
+        let mut unchecked = run(vec![solved(1, "1"), solved(2, "3")], None);
+        unchecked.check_answers();
+        assert_eq!(unchecked.status, DayStatus::Ok);
+
+        let mut panicked = run(vec![solved(1, "1"), solved(2, "3")], expected());
+        panicked.status = DayStatus::Panicked;
+        panicked.check_answers();
+        assert_eq!(panicked.status, DayStatus::Panicked);
+    }
+
+    #[test]
+    fn solves_days_with_a_single_part() {
+        // e.g. day 25 only declares part one with `solution!(25, 1)`.
+        let parts = vec![solved(1, "42")];
+        let status = get_day_status(&output(true, &[]), &parts);
+        assert_eq!(status, DayStatus::Ok);
+
+        let mut run = DayRun {
+            status,
+            ..run(
+                parts,
+                Some(Answers {
+                    part_1: Some("42".into()),
+                    part_2: None,
+                }),
+            )
+        };
+        run.check_answers();
+        assert_eq!(run.status, DayStatus::Ok);
+        assert_eq!(format_answer(&run, 2), "-");
+    }
+
+    #[test]
+    fn formats_answers_for_the_summary() {
+        let run = run(vec![solved(1, "#..#\n.##."), unsolved(2, None)], None);
+        assert_eq!(format_answer(&run, 1), "▼");
+        assert_eq!(format_answer(&run, 2), "✖");
+
+        let run = DayRun {
+            parts: vec![solved(1, "42")],
+            ..run
+        };
+        assert_eq!(format_answer(&run, 1), "42");
+        assert_eq!(format_answer(&run, 2), "-");
+    }
 }
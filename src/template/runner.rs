@@ -1,35 +1,230 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cell::RefCell;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
+use std::io::{self, stdout, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::process::Output;
+use std::sync::OnceLock;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, hint, process, thread};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::answers::Answers;
+use crate::template::input::InputSource;
+use crate::template::output::{self, PartAnswer, Reporter};
+use crate::template::solution_args;
+use crate::template::stats::Stats;
+use crate::template::submissions::{self, SubmitTarget, Verdict};
+use crate::template::{aoc_cli, config, ocr, plausibility, profile, Day};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Set by `cargo flaky --perturb` to the number of busy threads started next to the solution.
+pub const PERTURB_ENV: &str = "AOC_PERTURB_THREADS";
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// Starts busy threads competing with the solution for CPU time if requested by `cargo flaky --perturb`,
+/// so that threads of the solution are scheduled differently than usual.
+pub fn perturb_threads() {
+    let Some(count) = env::var(PERTURB_ENV).ok().and_then(|x| x.parse().ok()) else {
+        return;
+    };
+
+    for _ in 0..count {
+        thread::spawn(|| loop {
+            hint::spin_loop();
+            thread::yield_now();
+        });
+    }
+}
+
+/// Read the input selected via `--input` or `--example`, falling back to the day's puzzle input.
+pub fn read_input(day: Day) -> String {
+    let source = &solution_args::get().input;
+
+    // invalid configurations and profiles are usage errors, as in the CLI.
+    if let Err(e) = config::init()
+        .map_err(|e| e.to_string())
+        .and_then(|_| profile::validate_active())
+    {
+        eprintln!("{e}");
+        process::exit(2);
+    }
+
+    if *source != InputSource::Puzzle {
+        output::reporter().info(&format!("Input: {source}"));
+    }
+
+    source.read(day).unwrap_or_else(|e| {
+        eprintln!("Could not read {source}: {e}");
+        process::exit(1);
+    })
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+/// The return value of a solution part.
+///
+/// Parts can either return an [`Option`], where `None` marks the part as not implemented,
+/// or a [`Result`], where the error is displayed in place of the answer. An error without a message,
+/// as returned by [`bail!()`](crate::bail), marks the part as not implemented.
+pub trait PartResult {
+    /// Returns the formatted answer, `Ok(None)` if there is none, or the formatted error.
+    fn to_answer(&self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        Ok(self.as_ref().map(ToString::to_string))
+    }
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        match self {
+            Ok(answer) => Ok(Some(answer.to_string())),
+            Err(e) => match flatten_message(&e.to_string()) {
+                message if message.is_empty() => Ok(None),
+                message => Err(message),
+            },
+        }
     }
 }
 
+/// Result lines are parsed line by line, so multiline messages (e.g. from `assert_eq!`) are flattened.
+fn flatten_message(message: &str) -> String {
+    message
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn run_part<I: Clone, R: PartResult>(func: impl Fn(I) -> R, input: I, day: Day, part: u8) {
+    run_part_with(output::reporter(), func, input, day, part);
+}
+
+fn run_part_with<I: Clone, R: PartResult>(
+    reporter: &dyn Reporter,
+    func: impl Fn(I) -> R,
+    input: I,
+    day: Day,
+    part: u8,
+) {
+    if !solution_args::get().includes_part(part) {
+        return;
+    }
+
+    let timed = catch_panic(|| {
+        run_timed(&func, input, |result| {
+            reporter.intermediate_result(part, &result.to_answer());
+        })
+    });
+
+    let (result, duration, samples, std_dev) = match timed {
+        Ok(timed) => timed,
+        Err(panic_message) => {
+            // a panicking part is reported like an error, so the remaining parts still run.
+            reporter.result(part, &Err(panic_message), "");
+            return;
+        }
+    };
+
+    let result: PartAnswer = result.to_answer();
+    reporter.result(
+        part,
+        &result,
+        &format_duration(&duration, samples, &std_dev),
+    );
+
+    if let Ok(Some(result)) = result {
+        let warnings = check_plausibility(&result, day, part);
+        for warning in &warnings {
+            reporter.warning(&format!("⚠ Part {part}: {warning}"));
+        }
+
+        PREVIOUS_ANSWERS.with(|answers| answers.borrow_mut().push((part, result.clone())));
+        submit_result(result, day, part, &warnings);
+    }
+}
+
+thread_local! {
+    /// Answers of the parts that already ran in this process.
+    static PREVIOUS_ANSWERS: RefCell<Vec<(u8, String)>> = const { RefCell::new(vec![]) };
+}
+
+/// Check an answer for signs of common bugs. Comparisons with the example and stored answers
+/// are only done for the puzzle input, as they are expected to match for other inputs.
+fn check_plausibility(answer: &str, day: Day, part: u8) -> Vec<plausibility::Warning> {
+    let is_puzzle_input = solution_args::get().input == InputSource::Puzzle;
+
+    let mut other_answers: Vec<(u8, String)> = PREVIOUS_ANSWERS.with(|x| x.borrow().clone());
+    let mut example_answer = None;
+
+    if is_puzzle_input {
+        let example_path = super::data_path("examples", &format!("{day}.txt"));
+        example_answer = Answers::read_for(&example_path)
+            .ok()
+            .and_then(|x| x.get(part).map(String::from));
+
+        let stored = Answers::read_for(&profile::get_input_path(day)).unwrap_or_default();
+        other_answers.extend(
+            (1..=2)
+                .filter_map(|p| Some((p, stored.get(p)?.to_string())))
+                .filter(|(p, _)| *p != part),
+        );
+    }
+
+    let other_answers: Vec<(u8, &str)> = other_answers
+        .iter()
+        .filter(|(p, _)| *p != part)
+        .map(|(p, x)| (*p, x.as_str()))
+        .collect();
+
+    plausibility::check(answer, example_answer.as_deref(), &other_answers)
+}
+
+thread_local! {
+    static PANIC_MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Run a function, returning the message and source location of a panic instead of unwinding further.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(|info| {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+
+        let location = info
+            .location()
+            .map(|l| format!(" at {}:{}:{}", l.file(), l.line(), l.column()))
+            .unwrap_or_default();
+
+        let message = flatten_message(&message);
+
+        PANIC_MESSAGE.with(|m| *m.borrow_mut() = Some(format!("panicked{location}: {message}")));
+    }));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    panic::set_hook(default_hook);
+
+    result.map_err(|_| {
+        PANIC_MESSAGE
+            .with(|m| m.borrow_mut().take())
+            .unwrap_or_else(|| "panicked".into())
+    })
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.
+///     both can be configured in the `[bench]` table of `aoc.toml`.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Duration) {
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -43,23 +238,26 @@ fn run_timed<I: Clone, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let run = if solution_args::get().time {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        (base_time, 1, Duration::ZERO)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, run.2)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Returns the mean duration, the number of samples and their standard deviation.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (Duration, u128, Duration) {
+    output::reporter().bench_started();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench = &config::get().bench;
+    let bench_iterations = (bench.time.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(bench.min_samples, bench.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -71,99 +269,211 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
+    #[allow(clippy::cast_precision_loss)]
+    let nanos: Vec<f64> = timers.iter().map(|x| x.as_nanos() as f64).collect();
+    let stats = Stats::of(&nanos).expect("at least one sample is taken");
+
     (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
+        Duration::from_secs_f64(stats.mean / 1e9),
         bench_iterations,
+        Duration::from_secs_f64(stats.std_dev / 1e9),
     )
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
-
-fn format_duration(duration: &Duration, samples: u128) -> String {
+/// Formats a duration like `(74.1ns)`, or `(74.1ns ± 3.2ns @ 100 samples)` if benched.
+/// This is parsed by the commands running solutions, e.g. to store timings.
+fn format_duration(duration: &Duration, samples: u128, std_dev: &Duration) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
-    }
-}
-
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
-    let is_intermediate_result = duration_str.is_empty();
-
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
-        }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
-        }
+        format!(" ({duration:.1?} ± {std_dev:.1?} @ {samples} samples)")
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
+/// Try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+///
+/// Multiline results are treated as letter art and submitted as the recognised letters.
+/// If the result raised plausibility warnings, the submission has to be confirmed unless `--force` is passed.
+fn submit_result(
+    result: String,
     day: Day,
     part: u8,
+    warnings: &[plausibility::Warning],
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+    let args = solution_args::get();
+
+    let part_submit = match args.submit? {
+        SubmitTarget::Part(part) => part,
+        SubmitTarget::Auto => resolve_unsolved_part(day)?,
+    };
 
-    if !args.contains(&"--submit".into()) {
+    if part_submit != part {
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
-
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    // letter art has to be submitted as the letters it depicts.
+    let result = if result.contains('\n') {
+        match ocr::recognise(&result) {
+            Ok(letters) => {
+                println!(
+                    "Recognised letters: {}",
+                    output::reporter().strong(&letters)
+                );
+                letters
+            }
+            Err(e) => {
+                eprintln!("Could not submit letter art: {e}");
+                process::exit(1);
+            }
+        }
+    } else {
+        result
     };
 
-    if part_submit != part {
+    if !warnings.is_empty() && !args.force && !confirm("Submit anyway?") {
+        println!("Submission cancelled, append `--force` to skip this check.");
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    println!("Submitting result via aoc-cli...");
+    let output = aoc_cli::submit(day, part, &result);
+
+    if let Ok(output) = &output {
+        let verdict = aoc_cli::get_verdict(output);
+
+        if let Err(e) = submissions::append_log(day, part, &result, verdict) {
+            eprintln!("Failed to log submission: {e}");
+        }
+
+        if verdict == Verdict::Correct {
+            store_answer(day, part, &result);
+
+            // the description of part two only becomes visible after part one was solved.
+            if let Err(e) = aoc_cli::download_puzzle(day) {
+                eprintln!("Failed to refresh puzzle: {e}");
+            }
+        }
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(output)
+}
+
+/// Determine the part that `--submit auto` submits, once per process:
+/// submitting part one must not cause part two to be submitted in the same run.
+fn resolve_unsolved_part(day: Day) -> Option<u8> {
+    static UNSOLVED_PART: OnceLock<Option<u8>> = OnceLock::new();
+
+    *UNSOLVED_PART.get_or_init(|| {
+        let puzzle = fs::read_to_string(profile::get_puzzle_path(day)).unwrap_or_default();
+        let part = submissions::next_unsolved_part(&puzzle, &submissions::read_log(day));
+
+        match part {
+            Some(part) => println!("Submitting part {part}, the next unsolved part."),
+            None => println!("Both parts are already solved, nothing to submit."),
+        }
+
+        part
+    })
+}
+
+/// Ask the user for confirmation on stdin. Anything but `y` or `yes` is treated as a no.
+fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = stdout().flush();
+
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Remember an accepted answer next to the input it belongs to, so the solution can be verified against it later.
+fn store_answer(day: Day, part: u8, answer: &str) {
+    let input_path = profile::get_input_path(day);
+    let mut answers = Answers::read_for(&input_path).unwrap_or_default();
+    answers.set(part, answer);
+
+    match answers.write_for(&input_path) {
+        Ok(()) => println!(
+            "Stored answer in \"{}\".",
+            Answers::path_for(&input_path).display()
+        ),
+        Err(e) => eprintln!("Failed to store answer: {e}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::{flatten_message, run_part_with};
+    use crate::{
+        bail, day,
+        template::{
+            output::{format_result, PartAnswer, Reporter},
+            solution_args::{self, SolutionArgs},
+            SolutionResult,
+        },
+    };
+
+    /// Records the result lines instead of printing them.
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl Reporter for Recorder {
+        fn result(&self, part: u8, answer: &PartAnswer, _duration: &str) {
+            let line = format_result(part, answer, "", |x| x.to_string());
+            self.0.lock().unwrap().push(line);
+        }
+    }
+
+    fn run<R: super::PartResult>(part: u8, func: impl Fn(&str) -> R, input: &str) -> Vec<String> {
+        solution_args::set(SolutionArgs::default());
+        let recorder = Recorder::default();
+        run_part_with(&recorder, func, input, day!(1), part);
+        recorder.0.into_inner().unwrap()
+    }
+
+    fn parse(input: &str) -> SolutionResult<u32> {
+        let Some((a, b)) = input.split_once(' ') else {
+            bail!("expected two numbers, found `{input}`");
+        };
+        Ok(a.parse::<u32>()? + b.parse::<u32>()?)
+    }
+
+    #[test]
+    fn prints_errors_in_place_of_answers() {
+        assert_eq!(
+            run(1, parse, "1"),
+            vec!["Part 1: ✖ expected two numbers, found `1`"]
+        );
+        assert_eq!(
+            run(2, parse, "1 x"),
+            vec!["Part 2: ✖ invalid digit found in string"]
+        );
+    }
+
+    #[test]
+    fn flattens_multiline_errors() {
+        let part = |_: &str| -> Result<u32, String> { Err("bad input:\n  line 3\n".into()) };
+        assert_eq!(run(1, part, ""), vec!["Part 1: ✖ bad input: line 3"]);
+        assert_eq!(flatten_message("a\n\n  b  \n"), "a b");
+    }
+
+    #[test]
+    fn marks_parts_bailing_without_message_as_not_implemented() {
+        let part = |_: &str| -> SolutionResult<u32> { bail!() };
+        assert_eq!(run(2, part, ""), vec!["Part 2: ✖"]);
+    }
 }
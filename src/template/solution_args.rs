@@ -0,0 +1,234 @@
+/// Command-line arguments of the solution binaries generated by [`solution!`](crate::solution).
+///
+/// These are usually passed by `cargo solve`, `cargo time` and `cargo all`, but the binaries can be run directly, too:
+///
+/// ```text
+/// cargo run --release --bin 01 -- --part 2 --example
+/// ```
+use std::{env, ffi::OsString, process, sync::OnceLock};
+
+use pico_args::Arguments;
+
+use crate::template::{
+    input::InputSource,
+    output::{self, OutputFormat},
+    submissions::SubmitTarget,
+};
+
+pub const USAGE: &str = "\
+Usage: <day> [OPTIONS]
+
+Options:
+  --part <1|2>             Only run the given part.
+  --time                   Benchmark each part.
+  --submit <1|2|auto>      Submit the answer of a part via aoc-cli.
+  --force                  Submit without confirming implausible answers.
+  --input <path|->         Read the input from a file, or from stdin with `-`.
+  --example [name]         Read the input from `data/examples/<day>[-<name>].txt`.
+  --format <format>        `pretty` (colors and progress), `plain` or `quiet` (answers only).
+                           Defaults to `pretty` in terminals, unless `NO_COLOR` is set.
+  --help                   Print this help.";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SolutionArgs {
+    pub part: Option<u8>,
+    pub time: bool,
+    pub submit: Option<SubmitTarget>,
+    pub force: bool,
+    pub input: InputSource,
+    /// The output format, detected automatically if not set.
+    pub format: Option<OutputFormat>,
+    pub help: bool,
+}
+
+impl SolutionArgs {
+    /// Parses the arguments passed to a solution binary, excluding the program name.
+    pub fn parse(args: Vec<OsString>) -> Result<Self, String> {
+        let mut args = Arguments::from_vec(args);
+
+        let parsed = SolutionArgs {
+            help: args.contains(["-h", "--help"]),
+            part: args
+                .opt_value_from_fn("--part", parse_part)
+                .map_err(|e| e.to_string())?,
+            time: args.contains("--time"),
+            submit: args
+                .opt_value_from_str("--submit")
+                .map_err(|e| e.to_string())?,
+            force: args.contains("--force"),
+            format: args
+                .opt_value_from_str("--format")
+                .map_err(|e| e.to_string())?,
+            // parsed last, as it consumes the optional example name.
+            input: InputSource::from_args(&mut args)?,
+        };
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            return Err(format!("unexpected argument(s): {remaining:?}."));
+        }
+
+        if parsed.submit.is_some() && parsed.input != InputSource::Puzzle {
+            return Err("`--submit` can only be used with the puzzle input.".into());
+        }
+
+        if let (Some(part), Some(SubmitTarget::Part(submit))) = (parsed.part, parsed.submit) {
+            if part != submit {
+                return Err(format!(
+                    "`--submit {submit}` can not be used with `--part {part}`."
+                ));
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Converts the arguments back into a list that can be passed to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(part) = self.part {
+            args.extend(["--part".into(), part.to_string()]);
+        }
+        if self.time {
+            args.push("--time".into());
+        }
+        if let Some(submit) = self.submit {
+            args.extend(["--submit".into(), submit.to_string()]);
+        }
+        if self.force {
+            args.push("--force".into());
+        }
+        if let Some(format) = self.format {
+            args.extend(["--format".into(), format.to_string()]);
+        }
+        if self.help {
+            args.push("--help".into());
+        }
+
+        // the optional example name has to come last.
+        args.extend(self.input.to_args());
+        args
+    }
+
+    /// Whether a part should run.
+    pub fn includes_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|x| x == part)
+    }
+}
+
+fn parse_part(s: &str) -> Result<u8, String> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        x => Err(format!("expecting `1` or `2`, found `{x}`.")),
+    }
+}
+
+static ARGS: OnceLock<SolutionArgs> = OnceLock::new();
+
+/// Sets the arguments instead of parsing them from the command line, which is not possible in tests.
+/// Has no effect once they were parsed.
+#[cfg(feature = "test_lib")]
+pub(crate) fn set(args: SolutionArgs) {
+    let _ = ARGS.set(args);
+}
+
+/// Returns the arguments of the running solution binary.
+/// They are parsed once, selecting the output format; on `--help` or invalid arguments the usage is printed
+/// and the process exits.
+pub fn get() -> &'static SolutionArgs {
+    ARGS.get_or_init(
+        || match SolutionArgs::parse(env::args_os().skip(1).collect()) {
+            Ok(args) if args.help => {
+                println!("{USAGE}");
+                process::exit(0);
+            }
+            Ok(args) => {
+                if let Some(format) = args.format {
+                    output::set_format(format);
+                }
+                args
+            }
+            Err(e) => {
+                eprintln!("Error: {e}\n\n{USAGE}");
+                // the exit code of usage errors, as in the CLI.
+                process::exit(2);
+            }
+        },
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::SolutionArgs;
+    use crate::template::{input::InputSource, output::OutputFormat, submissions::SubmitTarget};
+
+    fn parse(s: &str) -> Result<SolutionArgs, String> {
+        SolutionArgs::parse(s.split_whitespace().map(Into::into).collect())
+    }
+
+    #[test]
+    fn parses_defaults() {
+        assert_eq!(parse(""), Ok(SolutionArgs::default()));
+    }
+
+    #[test]
+    fn parses_all_options() {
+        let args = parse("--part 2 --time --submit auto --force --format plain").unwrap();
+        assert_eq!(
+            args,
+            SolutionArgs {
+                part: Some(2),
+                time: true,
+                submit: Some(SubmitTarget::Auto),
+                force: true,
+                input: InputSource::Puzzle,
+                format: Some(OutputFormat::Plain),
+                help: false,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_examples_after_other_options() {
+        let args = parse("--example large --time").unwrap();
+        assert_eq!(args.input, InputSource::Example(Some("large".into())));
+        assert!(args.time);
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert!(parse("--part 3").is_err());
+        assert!(parse("--submit").is_err());
+        assert!(parse("--submit 3").is_err());
+        assert!(parse("--format fancy").is_err());
+        assert!(parse("--bogus").is_err());
+        assert!(parse("--submit 1 --example").is_err());
+        assert!(parse("--submit 1 --part 2").is_err());
+    }
+
+    #[test]
+    fn round_trips_arguments() {
+        let args = SolutionArgs {
+            part: Some(1),
+            time: true,
+            submit: Some(SubmitTarget::Part(1)),
+            force: true,
+            input: InputSource::Puzzle,
+            format: Some(OutputFormat::Plain),
+            help: false,
+        };
+        let list = args.to_args().into_iter().map(Into::into).collect();
+        assert_eq!(SolutionArgs::parse(list), Ok(args));
+
+        let args = SolutionArgs {
+            input: InputSource::Example(Some("2".into())),
+            ..Default::default()
+        };
+        let list = args.to_args().into_iter().map(Into::into).collect();
+        assert_eq!(SolutionArgs::parse(list), Ok(args));
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt::{Debug, Display};
+
+/// A lightweight error type for solutions, e.g. to report malformed input instead of panicking.
+///
+/// Any [`std::error::Error`] converts into it, so `?` can be used on parse results:
+///
+/// ```
+/// # use advent_of_code::template::SolutionResult;
+/// fn part_one(input: &str) -> SolutionResult<u32> {
+///     let n: u32 = input.trim().parse()?;
+///     Ok(n * 2)
+/// }
+/// ```
+pub struct SolutionError(String);
+
+/// A [`Result`] returned by solution parts.
+pub type SolutionResult<T> = Result<T, SolutionError>;
+
+impl SolutionError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+}
+
+// NOTE: `SolutionError` does not implement `std::error::Error` itself, so it can be created from any error.
+impl<E: std::error::Error> From<E> for SolutionError {
+    fn from(e: E) -> Self {
+        Self(e.to_string())
+    }
+}
+
+impl From<SolutionError> for String {
+    fn from(e: SolutionError) -> Self {
+        e.0
+    }
+}
+
+impl Display for SolutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Debug for SolutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+/// Returns early from a solution part with a [`SolutionError`] built from a format string.
+/// The error is converted with [`Into`], so this also works for parts returning `Result<T, String>`.
+/// Without a message, the part is marked as not implemented, like a part returning `None`.
+///
+/// ```
+/// # use advent_of_code::{bail, template::SolutionResult};
+/// fn parse_line(line: &str) -> SolutionResult<(u32, u32)> {
+///     let Some((a, b)) = line.split_once(' ') else {
+///         bail!("expected two numbers, found `{line}`");
+///     };
+///     Ok((a.parse()?, b.parse()?))
+/// }
+/// ```
+#[macro_export]
+macro_rules! bail {
+    () => {
+        return Err($crate::template::SolutionError::new(String::new()).into())
+    };
+    ($($arg:tt)*) => {
+        return Err($crate::template::SolutionError::new(format!($($arg)*)).into())
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{SolutionError, SolutionResult};
+
+    fn parse(s: &str) -> SolutionResult<u32> {
+        if s.is_empty() {
+            crate::bail!("empty input");
+        }
+        Ok(s.parse()?)
+    }
+
+    #[test]
+    fn converts_errors() {
+        assert_eq!(
+            parse("x").unwrap_err().to_string(),
+            "invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn bails_with_message() {
+        assert_eq!(parse("").unwrap_err().to_string(), "empty input");
+        assert_eq!(parse("42").unwrap(), 42);
+    }
+
+    #[test]
+    fn bails_without_message() {
+        fn part() -> SolutionResult<u32> {
+            crate::bail!();
+        }
+        assert_eq!(part().unwrap_err().to_string(), "");
+    }
+
+    #[test]
+    fn creates_errors() {
+        assert_eq!(SolutionError::new("foo").to_string(), "foo");
+    }
+}
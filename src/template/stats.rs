@@ -0,0 +1,66 @@
+/// Summary statistics of repeated measurements, e.g. the durations of a part across runs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub mean: f64,
+    /// The sample standard deviation, `0` for a single sample.
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Stats {
+    /// Returns `None` if there are no values.
+    pub fn of(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let samples = values.len();
+        let mean = values.iter().sum::<f64>() / samples as f64;
+        let variance = if samples > 1 {
+            values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (samples - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(Stats {
+            samples,
+            mean,
+            std_dev: variance.sqrt(),
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+
+    /// The standard deviation relative to the mean.
+    pub fn coefficient_of_variation(&self) -> f64 {
+        if self.mean == 0.0 {
+            0.0
+        } else {
+            self.std_dev / self.mean
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stats;
+
+    #[test]
+    fn summarizes_values() {
+        assert_eq!(Stats::of(&[]), None);
+
+        let stats = Stats::of(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.mean, 5.0);
+        assert!((stats.std_dev - 2.138_089_935).abs() < 1e-6);
+        assert_eq!((stats.min, stats.max), (2.0, 9.0));
+        assert!((stats.coefficient_of_variation() - 0.427_617_987).abs() < 1e-6);
+
+        let single = Stats::of(&[3.0]).unwrap();
+        assert_eq!(single.std_dev, 0.0);
+    }
+}
@@ -0,0 +1,259 @@
+/// Tracks submitted answers, to find out which part of a puzzle is still unsolved.
+///
+/// Submissions are logged next to the input they belong to, e.g. `data/inputs/01.submissions`:
+///
+/// ```text
+/// 1701406800 1 incorrect 4294967295
+/// 1701406860 1 correct 1651298
+/// ```
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::{profile, Day};
+
+/// The part selected with `--submit`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmitTarget {
+    Part(u8),
+    /// The first part that has not been solved yet.
+    Auto,
+}
+
+impl FromStr for SubmitTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Self::Part(1)),
+            "2" => Ok(Self::Part(2)),
+            "auto" => Ok(Self::Auto),
+            x => Err(format!("expecting `1`, `2` or `auto`, found `{x}`.")),
+        }
+    }
+}
+
+impl Display for SubmitTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Part(part) => write!(f, "{part}"),
+            Self::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+/// The response of the AoC server to a submission.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    /// Any other response, e.g. a rate limit.
+    Unknown,
+}
+
+impl Verdict {
+    /// Determine the verdict from the output aoc-cli printed for a submission.
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Self::Correct
+        } else if response.contains("That's not the right answer") {
+            Self::Incorrect
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Incorrect => write!(f, "incorrect"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Self::Correct),
+            "incorrect" => Ok(Self::Incorrect),
+            "unknown" => Ok(Self::Unknown),
+            x => Err(format!("unknown verdict `{x}`.")),
+        }
+    }
+}
+
+/// A single logged submission.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub timestamp: u64,
+    pub part: u8,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+impl FromStr for Submission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.splitn(4, ' ');
+        let mut next = |name: &str| {
+            fields
+                .next()
+                .ok_or_else(|| format!("expected submission to have a {name}."))
+        };
+
+        Ok(Submission {
+            timestamp: next("timestamp")?
+                .parse()
+                .map_err(|_| "invalid timestamp.")?,
+            part: next("part")?.parse().map_err(|_| "invalid part.")?,
+            verdict: next("verdict")?.parse()?,
+            answer: next("answer")?.to_string(),
+        })
+    }
+}
+
+impl Display for Submission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.timestamp, self.part, self.verdict, self.answer
+        )
+    }
+}
+
+/// Returns the path of the submission log that belongs to an input file.
+pub fn get_log_path(input_path: &Path) -> PathBuf {
+    input_path.with_extension("submissions")
+}
+
+/// Reads all logged submissions for a day's input, skipping malformed lines.
+pub fn read_log(day: Day) -> Vec<Submission> {
+    fs::read_to_string(get_log_path(&profile::get_input_path(day)))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.parse().ok())
+        .collect()
+}
+
+/// Appends a submission to the log of a day's input.
+pub fn append_log(day: Day, part: u8, answer: &str, verdict: Verdict) -> Result<(), io::Error> {
+    let submission = Submission {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        part,
+        verdict,
+        answer: answer.to_string(),
+    };
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(get_log_path(&profile::get_input_path(day)))?;
+
+    writeln!(file, "{submission}")
+}
+
+/// Counts the parts a downloaded puzzle description shows an accepted answer for.
+fn count_solved_in_puzzle(puzzle: &str) -> usize {
+    puzzle.matches("Your puzzle answer was").count()
+}
+
+/// Returns the first part that is neither solved according to the puzzle description nor the submission log,
+/// or `None` if both parts are solved.
+pub fn next_unsolved_part(puzzle: &str, log: &[Submission]) -> Option<u8> {
+    let solved_in_puzzle = count_solved_in_puzzle(puzzle);
+
+    (1..=2).find(|&part| {
+        let solved = usize::from(part) <= solved_in_puzzle
+            || log
+                .iter()
+                .any(|s| s.part == part && s.verdict == Verdict::Correct);
+        !solved
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{next_unsolved_part, Submission, SubmitTarget, Verdict};
+
+    fn submission(part: u8, verdict: Verdict) -> Submission {
+        Submission {
+            timestamp: 1_701_406_800,
+            part,
+            verdict,
+            answer: "42".into(),
+        }
+    }
+
+    #[test]
+    fn parses_submit_targets() {
+        assert_eq!("1".parse(), Ok(SubmitTarget::Part(1)));
+        assert_eq!("auto".parse(), Ok(SubmitTarget::Auto));
+        assert!("3".parse::<SubmitTarget>().is_err());
+    }
+
+    #[test]
+    fn round_trips_submissions() {
+        let mut s = submission(2, Verdict::Incorrect);
+        s.answer = "with spaces".into();
+        assert_eq!(s.to_string().parse::<Submission>(), Ok(s));
+    }
+
+    #[test]
+    fn detects_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer"),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too high."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently"),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn finds_unsolved_part_from_puzzle() {
+        assert_eq!(next_unsolved_part("--- Day 1 ---", &[]), Some(1));
+        assert_eq!(
+            next_unsolved_part("Your puzzle answer was `42`.", &[]),
+            Some(2)
+        );
+        assert_eq!(
+            next_unsolved_part(
+                "Your puzzle answer was `42`. Your puzzle answer was `7`.",
+                &[]
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn finds_unsolved_part_from_log() {
+        let log = [
+            submission(1, Verdict::Incorrect),
+            submission(1, Verdict::Correct),
+            submission(2, Verdict::Incorrect),
+        ];
+        assert_eq!(next_unsolved_part("", &log), Some(2));
+        assert_eq!(next_unsolved_part("", &log[..1]), Some(1));
+    }
+}
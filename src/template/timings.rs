@@ -1,39 +1,79 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fmt::Display, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
+use crate::template::history::History;
 use crate::template::Day;
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+/// The outcome of running a single part of a solution.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The part did not report a result, e.g. because it is not part of the solution.
+    #[default]
+    Missing,
+    Solved,
+    /// The part returned `None`.
+    NotImplemented,
+    /// The part returned an error.
+    Failed,
+    Panicked,
+}
+
+/// Benchmark of a single part. Durations are in nanoseconds, and only formatted for display.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PartTiming {
+    /// The mean duration of a sample.
+    pub nanos: f64,
+    /// The number of samples, unknown for timings migrated from version 1.
+    pub samples: Option<u64>,
+    /// The standard deviation of the samples, unknown for timings migrated from version 1.
+    pub std_dev: Option<f64>,
+}
+
+impl Display for PartTiming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format_nanos(self.nanos))
+    }
+}
 
 /// Represents benchmark times for a single day.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
+    /// Outcome of each part, not persisted to the timings file.
+    pub status: [PartStatus; 2],
+}
+
+impl Timing {
+    pub fn is_panicked(&self) -> bool {
+        self.status.contains(&PartStatus::Panicked)
+    }
+
+    /// The timing of the first or second part, if reported.
+    pub fn part(&self, part: u8) -> Option<PartTiming> {
+        match part {
+            1 => self.part_1,
+            2 => self.part_2,
+            _ => None,
+        }
+    }
 }
 
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Timings {
     pub data: Vec<Timing>,
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
-        let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
-        json.format_to(&mut file)
-    }
-
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
+    /// Rehydrate the latest timings of each day from the history in the timings file.
+    /// If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
+        History::read_from_file()
+            .map(|history| history.latest())
             .unwrap_or_default()
     }
 
@@ -67,30 +107,46 @@ impl Timings {
     }
 }
 
-/* -------------------------------------------------------------------------- */
-
-impl From<Timings> for JsonValue {
-    fn from(value: Timings) -> Self {
-        let mut map: HashMap<String, JsonValue> = HashMap::new();
+/// Parse a duration printed by a solution bin (e.g. `74.13ms`) to nanoseconds.
+pub fn parse_nanos(str_timing: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match str_timing {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
 
-        map.insert(
-            "data".into(),
-            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
-        );
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
 
-        JsonValue::Object(map)
-    }
+/// Formats nanoseconds like durations printed by solution bins, e.g. `74.1ms`.
+pub fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.max(0.0).round() as u64);
+    format!("{duration:.1?}")
 }
 
+/* -------------------------------------------------------------------------- */
+
 impl TryFrom<String> for Timings {
     type Error = String;
 
+    /// Reads the latest timings of each day from a timings file of any version.
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        History::try_from(value).map(|history| history.latest())
+    }
+}
 
+impl Timings {
+    /// Reads the `data` of a JSON object holding timings of the given schema version.
+    pub(super) fn from_json(
+        json: &HashMap<String, JsonValue>,
+        version: u32,
+    ) -> Result<Self, String> {
         let json_data = json
-            .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
@@ -99,7 +155,7 @@ impl TryFrom<String> for Timings {
         Ok(Timings {
             data: json_data
                 .iter()
-                .map(Timing::try_from)
+                .map(|timing| Timing::from_json(timing, version))
                 .collect::<Result<_, _>>()?,
         })
     }
@@ -107,40 +163,83 @@ impl TryFrom<String> for Timings {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+
+        #[allow(clippy::cast_precision_loss)]
+        let samples = value.samples.map(|x| x as f64);
+
+        map.insert(
+            "samples".into(),
+            samples.map_or(JsonValue::Null, JsonValue::Number),
+        );
+        map.insert(
+            "std_dev".into(),
+            value.std_dev.map_or(JsonValue::Null, JsonValue::Number),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let number = |key: &str| -> Result<Option<f64>, String> {
+            match json.get(key) {
+                None => Ok(None),
+                Some(v) if v.is_null() => Ok(None),
+                Some(v) => v.get::<f64>().copied().map(Some).ok_or(format!(
+                    "Expected part timing.{key} to be null or a number."
+                )),
+            }
+        };
+
+        Ok(PartTiming {
+            nanos: number("nanos")?.ok_or("Expected part timing.nanos to be a number.")?,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            samples: number("samples")?.map(|x| x as u64),
+            std_dev: number("std_dev")?,
+        })
+    }
+}
+
 impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
-
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
         map.insert(
             "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            value
+                .part_1
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
         );
-
         map.insert(
             "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            value
+                .part_2
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
         );
 
         JsonValue::Object(map)
     }
 }
 
-impl TryFrom<&JsonValue> for Timing {
-    type Error = String;
-
-    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+impl Timing {
+    /// Reads a timing of the given schema version, migrating formatted durations of version 1.
+    fn from_json(value: &JsonValue, version: u32) -> Result<Self, String> {
         let json = value
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
@@ -151,15 +250,30 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        let part = |key: &str| -> Result<Option<PartTiming>, String> {
+            let value = json
+                .get(key)
+                .ok_or(format!("Expected timing.{key} to be present."))?;
+
+            if value.is_null() {
+                return Ok(None);
+            }
+
+            if version == 1 {
+                let nanos = value
+                    .get::<String>()
+                    .and_then(|x| parse_nanos(x))
+                    .ok_or(format!("Expected timing.{key} to be null or a duration."))?;
+
+                return Ok(Some(PartTiming {
+                    nanos,
+                    samples: None,
+                    std_dev: None,
+                }));
+            }
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+            PartTiming::try_from(value).map(Some)
+        };
 
         let total_nanos = json
             .get("total_nanos")
@@ -168,9 +282,10 @@ impl TryFrom<&JsonValue> for Timing {
 
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
             total_nanos,
+            status: [PartStatus::default(); 2],
         })
     }
 }
@@ -181,46 +296,93 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
+
+    /// A benchmarked part taking the given milliseconds.
+    pub fn millis(millis: f64) -> PartTiming {
+        PartTiming {
+            nanos: millis * 1_000_000.0,
+            samples: Some(100),
+            std_dev: Some(millis * 1_000.0),
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(millis(10.0)),
+                    part_2: Some(millis(20.0)),
                     total_nanos: 3e+10,
+                    status: Default::default(),
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(millis(30.0)),
+                    part_2: Some(millis(40.0)),
                     total_nanos: 7e+10,
+                    status: Default::default(),
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    part_1: Some(millis(40.0)),
                     part_2: None,
                     total_nanos: 4e+10,
+                    status: Default::default(),
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{PartTiming, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1000000, "samples": 10, "std_dev": 2000 }, "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(
+                timing.part_1,
+                Some(PartTiming {
+                    nanos: 1_000_000_f64,
+                    samples: Some(10),
+                    std_dev: Some(2000_f64),
+                })
+            );
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.total_nanos, 1_000_000_f64);
+        }
+
+        #[test]
+        fn migrates_version_1() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "74.13µs", "total_nanos": 1074130 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.part_1,
+                Some(PartTiming {
+                    nanos: 1_000_000_f64,
+                    samples: None,
+                    std_dev: None,
+                })
+            );
+            assert!((timing.part_2.unwrap().nanos - 74_130_f64).abs() < 1e-6);
+        }
+
+        #[test]
+        fn rejects_unsupported_versions() {
+            let json = r#"{ "version": 4, "runs": [] }"#.to_string();
+            assert_eq!(
+                Timings::try_from(json),
+                Err("unsupported version 4, expected at most 3.".into())
+            );
         }
 
         #[test]
@@ -247,15 +409,77 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::{
+            day,
+            template::history::{Environment, History},
+            template::timings::{format_nanos, PartTiming, Timing, Timings},
+        };
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        fn to_json(timings: Timings) -> JsonValue {
+            let mut history = History::default();
+            history.push(Environment::default(), timings);
+            JsonValue::from(&history)
+        }
+
+        fn round_trip(timings: Timings) -> Timings {
+            let json = to_json(timings).stringify().unwrap();
+            Timings::try_from(json).unwrap()
+        }
+
         #[test]
-        fn serializes_timings() {
+        fn round_trips_timings() {
             let timings = get_mock_timings();
-            let value = JsonValue::try_from(timings).unwrap();
+            assert_eq!(round_trip(timings.clone()), timings);
+        }
+
+        #[test]
+        fn round_trips_unknown_stats() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(25),
+                    part_1: Some(PartTiming {
+                        nanos: 74.13,
+                        samples: None,
+                        std_dev: None,
+                    }),
+                    part_2: None,
+                    total_nanos: 74.13,
+                    status: Default::default(),
+                }],
+            };
+            assert_eq!(round_trip(timings.clone()), timings);
+            assert_eq!(round_trip(Timings::default()), Timings::default());
+        }
+
+        #[test]
+        fn round_trips_migrated_timings() {
+            let json = r#"{ "data": [{ "day": "03", "part_1": "2.5s", "part_2": null, "total_nanos": 2500000000 }] }"#.to_string();
+            let migrated = Timings::try_from(json).unwrap();
+            assert_eq!(round_trip(migrated.clone()), migrated);
+        }
+
+        #[test]
+        fn formats_durations_for_display() {
+            assert_eq!(format_nanos(74.13), "74.0ns");
+            assert_eq!(format_nanos(74_130_000.0), "74.1ms");
+            assert_eq!(
+                get_mock_timings().data[0].part_1.unwrap().to_string(),
+                "10.0ms"
+            );
+        }
+
+        #[test]
+        fn serializes_timings() {
+            let value = to_json(get_mock_timings());
+            let json = value.get::<HashMap<String, JsonValue>>().unwrap();
+            assert_eq!(json.get("version"), Some(&JsonValue::Number(3.0)));
+
+            let runs = json.get("runs").unwrap().get::<Vec<JsonValue>>().unwrap();
+            assert_eq!(runs.len(), 1);
             assert_eq!(
-                value
+                runs[0]
                     .get::<HashMap<String, JsonValue>>()
                     .unwrap()
                     .get("data")
@@ -269,6 +493,7 @@ mod tests {
     }
 
     mod is_day_complete {
+        use super::millis;
         use crate::{
             day,
             template::timings::{Timing, Timings},
@@ -279,13 +504,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: Some(millis(1.0)),
+                    part_2: Some(millis(2.0)),
                     total_nanos: 3_000_000_000_f64,
+                    status: Default::default(),
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +519,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    part_1: Some(millis(1.0)),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    status: Default::default(),
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -310,10 +537,11 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    status: Default::default(),
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -334,6 +562,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    status: Default::default(),
                 }],
             };
             let merged = timings.merge(&other);
@@ -354,6 +583,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    status: Default::default(),
                 }],
             };
             let merged = timings.merge(&other);
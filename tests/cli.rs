@@ -0,0 +1,490 @@
+//! End-to-end tests of the CLI commands against a fake aoc-cli.
+#![cfg(all(unix, feature = "test_lib"))]
+
+mod common;
+
+use common::{stderr, stdout, Workspace};
+
+const SOLUTION: &str = "advent_of_code::solution!(1);
+
+pub fn part_one(input: &str) -> Option<usize> {
+    Some(input.lines().count() * 21)
+}
+
+pub fn part_two(_input: &str) -> Option<usize> {
+    None
+}
+";
+
+#[test]
+fn scaffolds_a_day() {
+    let workspace = Workspace::empty("scaffold");
+    workspace.write("src/bin/.gitkeep", "");
+
+    let output = workspace.run(&["scaffold", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(workspace
+        .read("src/bin/01.rs")
+        .starts_with("advent_of_code::solution!(1);"));
+    assert_eq!(workspace.read("data/inputs/01.txt"), "");
+    assert_eq!(workspace.read("data/examples/01.txt"), "");
+    assert!(workspace.aoc_calls().is_empty());
+
+    // existing solutions are only replaced with `--overwrite`.
+    workspace.write("src/bin/01.rs", SOLUTION);
+    let output = workspace.run(&["scaffold", "1"]);
+    assert_eq!(output.status.code(), Some(4));
+    assert_eq!(workspace.read("src/bin/01.rs"), SOLUTION);
+
+    let output = workspace.run(&["scaffold", "1", "--overwrite"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_ne!(workspace.read("src/bin/01.rs"), SOLUTION);
+}
+
+#[test]
+fn scaffolds_a_day_without_profile_input() {
+    let workspace = Workspace::empty("scaffold-profile");
+    workspace.write("src/bin/.gitkeep", "");
+
+    let output = workspace.run(&["scaffold", "1", "--profile", "alice"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(workspace.exists("src/bin/01.rs"));
+    // an empty input would be picked up by `cargo verify` as part of the corpus.
+    assert!(!workspace.exists("data/inputs/01/alice.txt"));
+    assert!(!workspace.exists("data/inputs/01.txt"));
+}
+
+#[test]
+fn scaffolds_and_downloads_a_day() {
+    let workspace = Workspace::empty("scaffold-download");
+    workspace.write("src/bin/.gitkeep", "");
+
+    let output = workspace.run(&["scaffold", "1", "--download"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(workspace.exists("src/bin/01.rs"));
+    assert_eq!(workspace.read("data/inputs/01.txt"), "3   4\n4   3\n");
+    assert_eq!(
+        workspace.read("data/puzzles/01.md"),
+        "--- Day 1: Fake ---\n"
+    );
+}
+
+#[test]
+fn downloads_with_year_from_environment() {
+    let workspace = Workspace::empty("download-year");
+
+    let output = workspace
+        .command(&["download", "1"])
+        .env("AOC_YEAR", "2023")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        workspace.aoc_calls(),
+        [
+            "-V",
+            "--overwrite --input-file data/inputs/01.txt --puzzle-file data/puzzles/01.md --year 2023 --day 01 download"
+        ]
+    );
+    assert_eq!(workspace.read("data/inputs/01.txt"), "3   4\n4   3\n");
+    assert!(stdout(&output).contains("Successfully wrote input to \"data/inputs/01.txt\"."));
+}
+
+#[test]
+fn downloads_without_year() {
+    let workspace = Workspace::empty("download-no-year");
+
+    let output = workspace.run(&["download", "2"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        workspace.aoc_calls()[1],
+        "--overwrite --input-file data/inputs/02.txt --puzzle-file data/puzzles/02.md --day 02 download"
+    );
+}
+
+#[test]
+fn downloads_multiple_days() {
+    let workspace = Workspace::empty("download-days");
+
+    let output = workspace.run(&["download", "1,3"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(workspace.exists("data/inputs/01.txt"));
+    assert!(!workspace.exists("data/inputs/02.txt"));
+    assert!(workspace.exists("data/inputs/03.txt"));
+}
+
+#[test]
+fn downloads_into_profile() {
+    let workspace = Workspace::empty("download-profile");
+
+    let output = workspace
+        .command(&["download", "1", "--profile", "alice"])
+        .env("HOME", "/home/alice")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        workspace.aoc_calls()[1],
+        "--overwrite --input-file data/inputs/01/alice.txt --puzzle-file data/puzzles/01/alice.md \
+        --session-file /home/alice/.adventofcode.alice.session --day 01 download"
+    );
+    assert!(workspace.exists("data/inputs/01/alice.txt"));
+}
+
+#[test]
+fn fails_if_aoc_cli_fails() {
+    let workspace = Workspace::empty("download-failure");
+
+    let output = workspace
+        .command(&["download", "1"])
+        .env("AOC_FAKE_EXIT", "1")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    assert!(stderr(&output).contains("failed to call aoc-cli"));
+}
+
+#[test]
+fn reads_puzzle() {
+    let workspace = Workspace::empty("read");
+
+    let output = workspace
+        .command(&["read", "1"])
+        .env("AOC_YEAR", "2023")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("--- Day 1: Fake ---"));
+    assert_eq!(
+        workspace.aoc_calls()[1],
+        "--description-only --puzzle-file data/puzzles/01.md --year 2023 --day 01 read"
+    );
+}
+
+#[test]
+fn submits_correct_answer() {
+    let workspace = Workspace::template("submit-correct");
+    workspace.write("src/bin/01.rs", SOLUTION);
+    workspace.write("data/inputs/01.txt", "3   4\n4   3\n");
+
+    let output = workspace
+        .command(&["solve", "1", "--submit", "1"])
+        .env("AOC_YEAR", "2023")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Part 1: 42"));
+
+    let calls = workspace.aoc_calls();
+    assert!(calls.contains(&"--year 2023 --day 01 submit 1 42".to_string()));
+    assert!(calls.contains(
+        &"--overwrite --puzzle-only --puzzle-file data/puzzles/01.md --year 2023 --day 01 download"
+            .to_string()
+    ));
+
+    assert_eq!(workspace.read("data/inputs/01.answers"), "1: 42\n2: -\n");
+    assert!(workspace
+        .read("data/inputs/01.submissions")
+        .ends_with(" 1 correct 42\n"));
+}
+
+#[test]
+fn logs_incorrect_answer() {
+    let workspace = Workspace::template("submit-incorrect");
+    workspace.write("src/bin/01.rs", SOLUTION);
+    workspace.write("data/inputs/01.txt", "3   4\n4   3\n");
+
+    let output = workspace
+        .command(&["solve", "1", "--submit", "1"])
+        .env("AOC_FAKE_RESPONSE", "That's not the right answer.")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(workspace
+        .aoc_calls()
+        .contains(&"--day 01 submit 1 42".to_string()));
+    assert!(!workspace.exists("data/inputs/01.answers"));
+    assert!(workspace
+        .read("data/inputs/01.submissions")
+        .ends_with(" 1 incorrect 42\n"));
+}
+
+#[test]
+fn solves_from_a_subdirectory() {
+    let workspace = Workspace::template("solve-subdirectory");
+    workspace.write("src/bin/01.rs", SOLUTION);
+
+    let output = workspace
+        .command(&["solve", "1"])
+        .current_dir(workspace.dir.join("src/bin"))
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let input_path = workspace.dir.join("data/inputs/01.txt");
+    assert!(stderr(&output).contains(&format!(
+        "\"{}\" does not exist. Run `cargo download 01` to download the input.",
+        input_path.display()
+    )));
+
+    workspace.write("data/inputs/01.txt", "3   4\n4   3\n");
+    let output = workspace
+        .command(&["solve", "1"])
+        .current_dir(workspace.dir.join("src/bin"))
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Part 1: 42"));
+}
+
+#[test]
+fn checks_answers_of_solved_days() {
+    let workspace = Workspace::template("solve-check");
+    workspace.write("src/bin/01.rs", SOLUTION);
+    workspace.write("data/inputs/01.txt", "3   4\n4   3\n");
+    workspace.write("data/inputs/01.answers", "1: 42\n2: -\n");
+
+    let output = workspace.run(&["solve", "1", "--check"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    workspace.write("data/inputs/01.answers", "1: 43\n2: -\n");
+    let output = workspace.run(&["solve", "1", "--check"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("wrong answer"));
+}
+
+#[test]
+fn solutions_exit_with_2_on_invalid_arguments() {
+    let workspace = Workspace::template("solution-usage");
+    workspace.write("src/bin/01.rs", SOLUTION);
+
+    let output = workspace
+        .cargo(&["run", "--quiet", "--bin", "01", "--", "--part", "7"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("expecting `1` or `2`, found `7`."));
+}
+
+#[test]
+fn reports_answers_changed_by_mutated_inputs() {
+    let workspace = Workspace::template("robust");
+    workspace.write("src/bin/01.rs", SOLUTION);
+    workspace.write("data/inputs/01.txt", "3   4\n4   3\n");
+
+    let output = workspace.run(&["robust", "1"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    // counting lines is affected by blank lines, but not by line endings or whitespace.
+    let stdout = stdout(&output);
+    assert!(stdout.contains("  ✔ CRLF line endings\n"));
+    assert!(stdout.contains("  ✔ trailing spaces\n"));
+    assert!(stdout.contains("  ✖ extra blank line at the end\n    Part 1: 42 became 63\n"));
+    assert!(stderr(&output).contains("1 of 4 variants changed an answer or panicked."));
+}
+
+#[test]
+fn normalises_mutated_inputs_unless_raw() {
+    let workspace = Workspace::template("robust-raw");
+    workspace.write(
+        "src/bin/01.rs",
+        "advent_of_code::solution!(1);
+
+pub fn part_one(input: &str) -> Option<usize> {
+    Some(input.matches('\\r').count())
+}
+
+pub fn part_two(_input: &str) -> Option<usize> {
+    None
+}
+",
+    );
+    workspace.write("aoc.toml", "[input]\nnormalize = \"crlf\"\n");
+    workspace.write("data/inputs/01.txt", "3   4\n4   3\n");
+
+    // the configured normalisation removes the carriage returns of the variant.
+    let output = workspace.run(&["robust", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("  ✔ CRLF line endings\n"));
+
+    let output = workspace.run(&["robust", "1", "--raw"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = stdout(&output);
+    assert!(stdout.contains("without normalising them"));
+    assert!(stdout.contains("  ✖ CRLF line endings\n    Part 1: 0 became 2\n"));
+}
+
+#[test]
+fn flags_answers_depending_on_hash_order() {
+    let workspace = Workspace::template("flaky");
+    workspace.write(
+        "src/bin/01.rs",
+        "advent_of_code::solution!(1);
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let set: std::collections::HashSet<u32> = (0..1000).collect();
+    set.into_iter().next().map(|x| x + input.len() as u32)
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    Some(input.len())
+}
+",
+    );
+    workspace.write("data/inputs/01.txt", "3   4\n4   3\n");
+
+    let output = workspace.run(&["flaky", "1", "--runs", "3", "--perturb"]);
+    assert_eq!(output.status.code(), Some(1), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Part 1: ✖"));
+    assert!(stdout(&output).contains("Part 2: ✔ 12 in 3 runs"));
+    assert!(stderr(&output).contains("1 of 2 parts returned different results across runs."));
+}
+
+#[test]
+fn reports_overflows_in_checked_mode() {
+    let workspace = Workspace::template("checked");
+    workspace.write(
+        "src/bin/01.rs",
+        "advent_of_code::solution!(1);
+
+pub fn part_one(input: &str) -> Option<u8> {
+    Some(input.len() as u8 * 100)
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    Some(input.len())
+}
+",
+    );
+    workspace.write("data/inputs/01.txt", "3   4\n4   3\n");
+
+    // release builds silently wrap around.
+    let output = workspace.run(&["solve", "1", "--release"]);
+    assert!(stdout(&output).contains("Part 1: 176"));
+
+    let output = workspace.run(&["solve", "1", "--checked"]);
+    let stdout = stdout(&output);
+    assert!(stdout
+        .contains("Part 1: ✖ panicked at src/bin/01.rs:4:10: attempt to multiply with overflow"));
+    assert!(stdout.contains("Part 2: 12"));
+}
+
+#[test]
+fn stores_a_history_of_timings() {
+    let workspace = Workspace::template("timings-history");
+    workspace.write("src/bin/01.rs", SOLUTION);
+    workspace.write("data/inputs/01.txt", "3   4\n4   3\n");
+    workspace.write(
+        "data/timings.json",
+        r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1000000, "samples": 10, "std_dev": 2000 }, "part_2": null, "total_nanos": 1000000 }] }"#,
+    );
+
+    let output = workspace.run(&["time", "1", "--store"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let timings = workspace.read("data/timings.json");
+    assert!(timings.contains("\"version\": 3"), "{timings}");
+    assert!(timings.contains("\"profile\": \"release\""), "{timings}");
+
+    let output = workspace.run(&["timings", "history", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    let rows: Vec<&str> = stdout.lines().skip(3).collect();
+    assert_eq!(rows.len(), 2, "{stdout}");
+    // the run migrated from version 2 has an unknown environment.
+    assert!(rows[0].starts_with("-  "), "{stdout}");
+    assert!(rows[0].contains("1.0ms ± 2.0µs"), "{stdout}");
+    assert!(rows[1].contains(" release "), "{stdout}");
+}
+
+#[test]
+fn fails_on_regressions_against_stored_timings() {
+    let workspace = Workspace::template("compare");
+    workspace.write("src/bin/01.rs", SOLUTION);
+    workspace.write("data/inputs/01.txt", "3   4\n4   3\n");
+    let stored = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1, "samples": 100, "std_dev": 0.1 }, "part_2": null, "total_nanos": 1 }] }"#;
+    workspace.write("data/timings.json", stored);
+
+    let output = workspace.run(&["time", "1", "--fail-on-regression", "10"]);
+    assert_eq!(output.status.code(), Some(1), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.contains("Comparison"), "{stdout}");
+    assert!(stdout.contains("⚠ regression"), "{stdout}");
+    assert!(stderr(&output).contains("1 of 1 parts got slower by more than 10%."));
+
+    // comparing does not store the new timings.
+    assert_eq!(workspace.read("data/timings.json"), stored);
+}
+
+#[test]
+fn fails_if_stored_timings_can_not_be_read() {
+    let workspace = Workspace::template("corrupt-timings");
+    workspace.write("src/bin/01.rs", SOLUTION);
+    workspace.write("data/inputs/01.txt", "3   4\n4   3\n");
+    workspace.write("data/timings.json", "{ corrupt");
+
+    for args in [
+        &["time", "1", "--compare"][..],
+        &["time", "1", "--fail-on-regression", "10"],
+        &["time"],
+    ] {
+        let output = workspace.run(args);
+        assert_eq!(output.status.code(), Some(1), "{args:?}");
+        assert!(
+            stderr(&output).contains("Could not read \"data/timings.json\""),
+            "{}",
+            stderr(&output)
+        );
+    }
+
+    assert_eq!(workspace.read("data/timings.json"), "{ corrupt");
+}
+
+#[test]
+fn enforces_performance_budgets() {
+    let workspace = Workspace::template("budget");
+    workspace.write("src/bin/01.rs", SOLUTION);
+    workspace.write("data/inputs/01.txt", "3   4\n4   3\n");
+    workspace.write(
+        "aoc.toml",
+        "[budget]\ntotal = \"1s\"\n\n[budget.days]\n01 = \"1ns\"\n",
+    );
+
+    let output = workspace.run(&["time", "1", "--enforce-budget"]);
+    assert_eq!(output.status.code(), Some(1), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.contains("Budget"), "{stdout}");
+    assert!(stdout.contains("⚠ over budget"), "{stdout}");
+    assert!(stderr(&output).contains("1 of 2 budgets exceeded."));
+}
+
+#[test]
+fn enforces_budgets_of_days_that_are_not_benched_again() {
+    let workspace = Workspace::template("budget-stored");
+    workspace.write(
+        "src/bin/01.rs",
+        "advent_of_code::solution!(1);
+
+pub fn part_one(input: &str) -> Option<usize> {
+    Some(input.len())
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    Some(input.lines().count())
+}
+",
+    );
+    workspace.write("data/inputs/01.txt", "3   4\n4   3\n");
+    workspace.write(
+        "aoc.toml",
+        "[bench]\ntime_ms = 10\n\n[budget.days]\n01 = \"1ns\"\n",
+    );
+
+    let output = workspace.run(&["time", "1", "--store"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    // day 1 is fully benched, so it is skipped and only its stored timing is checked.
+    let output = workspace.run(&["time", "--enforce-budget"]);
+    assert_eq!(output.status.code(), Some(1), "{}", stderr(&output));
+    assert!(stdout(&output).contains("⚠ over budget"));
+    assert!(stderr(&output).contains("1 of 1 budgets exceeded."));
+}
@@ -0,0 +1,187 @@
+//! Test harness for the CLI: temporary workspaces with a scripted stand-in for aoc-cli on the `PATH`.
+#![allow(dead_code)]
+
+use std::{
+    env,
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+    process::{self, Command, Output},
+};
+
+/// Logs its arguments to `$AOC_FAKE_LOG` and mimics the commands of aoc-cli used by the template.
+/// `$AOC_FAKE_RESPONSE` replaces the response to a submission, `$AOC_FAKE_EXIT` makes every command fail.
+const FAKE_AOC: &str = r#"#!/bin/sh
+printf '%s\n' "$*" >> "$AOC_FAKE_LOG"
+
+if [ "$1" = "-V" ]; then
+    echo "aoc-cli 0.12.0"
+    exit 0
+fi
+
+if [ -n "$AOC_FAKE_EXIT" ]; then
+    echo "error: scripted failure" >&2
+    exit "$AOC_FAKE_EXIT"
+fi
+
+input=""
+puzzle=""
+command=""
+while [ $# -gt 0 ]; do
+    case "$1" in
+        --input-file) input="$2"; shift ;;
+        --puzzle-file) puzzle="$2"; shift ;;
+        download|read|submit) command="$1" ;;
+    esac
+    shift
+done
+
+case "$command" in
+    download)
+        if [ -n "$input" ]; then printf '3   4\n4   3\n' > "$input"; fi
+        if [ -n "$puzzle" ]; then printf -- '--- Day 1: Fake ---\n' > "$puzzle"; fi
+        ;;
+    read) echo "--- Day 1: Fake ---" ;;
+    submit) echo "${AOC_FAKE_RESPONSE:-That's the right answer! You are one gold star closer.}" ;;
+esac
+"#;
+
+/// A temporary directory the CLI runs in, removed again when dropped.
+pub struct Workspace {
+    pub dir: PathBuf,
+}
+
+impl Workspace {
+    /// An empty workspace, e.g. to check failures outside of a template repository.
+    pub fn empty(name: &str) -> Self {
+        let dir = env::temp_dir().join(format!("aoc-cli-tests-{}-{name}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join(".bin")).unwrap();
+
+        let aoc = dir.join(".bin/aoc");
+        fs::write(&aoc, FAKE_AOC).unwrap();
+        make_executable(&aoc);
+
+        Workspace { dir }
+    }
+
+    /// A copy of the template without any solutions, which can build and run solution binaries.
+    pub fn template(name: &str) -> Self {
+        let workspace = Self::empty(name);
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+        for file in [
+            "Cargo.toml",
+            "Cargo.lock",
+            "src/lib.rs",
+            "src/main.rs",
+            "src/template.txt",
+        ] {
+            workspace.write(file, &fs::read_to_string(root.join(file)).unwrap());
+        }
+        for dir in ["src/template", "src/util"] {
+            copy_dir(&root.join(dir), &workspace.dir.join(dir));
+        }
+        fs::create_dir_all(workspace.dir.join("src/bin")).unwrap();
+
+        workspace
+    }
+
+    /// A command running the CLI in this workspace, finding the fake aoc-cli first on the `PATH`.
+    pub fn command(&self, args: &[&str]) -> Command {
+        self.configure(Command::new(env!("CARGO_BIN_EXE_advent_of_code")), args)
+    }
+
+    /// A command running cargo in this workspace, e.g. to run a solution binary directly.
+    pub fn cargo(&self, args: &[&str]) -> Command {
+        self.configure(Command::new("cargo"), args)
+    }
+
+    fn configure(&self, mut command: Command, args: &[&str]) -> Command {
+        let mut path = OsString::from(self.dir.join(".bin"));
+        path.push(":");
+        path.push(env::var_os("PATH").unwrap_or_default());
+
+        command
+            .args(args)
+            .current_dir(&self.dir)
+            .env("CARGO_MANIFEST_DIR", &self.dir)
+            .env("PATH", path)
+            .env("AOC_FAKE_LOG", self.dir.join(".bin/calls"))
+            // solution binaries are built in a shared directory, so dependencies are only compiled once.
+            .env(
+                "CARGO_TARGET_DIR",
+                Path::new(env!("CARGO_MANIFEST_DIR")).join("target/cli-tests"),
+            )
+            .env("CARGO_NET_OFFLINE", "true")
+            .env_remove("AOC_YEAR")
+            .env_remove("AOC_PROFILE");
+        command
+    }
+
+    pub fn run(&self, args: &[&str]) -> Output {
+        self.command(args).output().unwrap()
+    }
+
+    /// The arguments of every call to the fake aoc-cli, one line per call.
+    pub fn aoc_calls(&self) -> Vec<String> {
+        fs::read_to_string(self.dir.join(".bin/calls"))
+            .unwrap_or_default()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    pub fn read(&self, path: &str) -> String {
+        fs::read_to_string(self.dir.join(path))
+            .unwrap_or_else(|e| panic!("could not read \"{path}\": {e}"))
+    }
+
+    pub fn write(&self, path: &str, contents: &str) {
+        let path = self.dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    pub fn exists(&self, path: &str) -> bool {
+        self.dir.join(path).exists()
+    }
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into()
+}
+
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into()
+}
+
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+
+    for entry in fs::read_dir(from).unwrap() {
+        let path = entry.unwrap().path();
+        let target = to.join(path.file_name().unwrap());
+
+        if path.is_dir() {
+            copy_dir(&path, &target);
+        } else {
+            fs::copy(&path, &target).unwrap();
+        }
+    }
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) {}
@@ -0,0 +1,112 @@
+//! Failure paths of the CLI and the exit codes they map to.
+#![cfg(feature = "test_lib")]
+
+mod common;
+
+use common::{stderr, Workspace};
+
+#[test]
+fn exits_with_2_on_usage_errors() {
+    let workspace = Workspace::empty("usage-errors");
+
+    let output = workspace.run(&["bogus"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("Unknown command: bogus"));
+
+    let output = workspace.run(&[]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = workspace.run(&["solve", "26"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("`26` is not a day"));
+
+    let output = workspace.run(&["solve", "1,2", "--submit", "1"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("can only be used with a single day"));
+
+    let output = workspace.run(&["solve", "1", "--bogus"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("Unknown argument(s): [\"--bogus\"]"));
+
+    let output = workspace.run(&["solve", "1", "--check", "--example"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("`--check` can not be used"));
+}
+
+#[test]
+fn exits_with_2_on_invalid_configs() {
+    let workspace = Workspace::empty("invalid-config");
+    workspace.write("aoc.toml", "[bench]\nmin_samples = 0\n");
+
+    let output = workspace.run(&["download", "1"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("`bench.min_samples` has to be at least 1"));
+    assert!(workspace.aoc_calls().is_empty());
+}
+
+#[test]
+fn exits_with_2_on_invalid_profiles_in_the_environment() {
+    let workspace = Workspace::empty("profile-env");
+
+    let output = workspace
+        .command(&["download", "1"])
+        .env("AOC_PROFILE", "../../x")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("AOC_PROFILE: invalid profile name `../../x`"));
+    assert!(workspace.aoc_calls().is_empty());
+}
+
+#[test]
+fn exits_with_3_if_aoc_cli_is_missing() {
+    let workspace = Workspace::empty("missing-aoc");
+
+    let output = workspace
+        .command(&["download", "1"])
+        .env("PATH", "")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    assert!(stderr(&output).contains("command \"aoc\" not found"));
+}
+
+#[test]
+fn exits_with_4_on_io_errors() {
+    // outside of a template workspace, `src/bin` does not exist.
+    let workspace = Workspace::empty("outside-workspace");
+
+    let output = workspace.run(&["scaffold", "1"]);
+    assert_eq!(output.status.code(), Some(4));
+    assert!(stderr(&output).contains("failed to create module file \"src/bin/01.rs\""));
+}
+
+#[test]
+fn exits_with_1_if_nothing_could_be_verified() {
+    let workspace = Workspace::empty("empty-corpus");
+
+    let output = workspace.run(&["verify", "1"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("No inputs to verify."));
+}
+
+#[test]
+fn exits_with_1_if_multiple_days_fail() {
+    let workspace = Workspace::empty("days-failures");
+
+    let output = workspace
+        .command(&["download", "1,2"])
+        .env("AOC_FAKE_EXIT", "1")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("2 of 2 days failed"));
+
+    // the second day is downloaded even though the first one failed.
+    let downloads = workspace
+        .aoc_calls()
+        .into_iter()
+        .filter(|call| call.contains("download"))
+        .count();
+    assert_eq!(downloads, 2);
+}